@@ -1,6 +1,9 @@
 use afrotd::{
+    extractor::ExtractorArgs,
     parser::RulesParser,
+    profile::EditionProfile,
     rule::{ArticleNr, Rule, RuleInterpretation},
+    standard::{Standard, StandardNr},
 };
 use clap::{Parser, ValueEnum};
 use eyre::eyre;
@@ -16,6 +19,7 @@ enum DeckType {
     RulesTemplates,
     #[default]
     Interpretations,
+    StandardsTemplates,
 }
 
 #[derive(Debug, Clone, Parser)]
@@ -29,6 +33,10 @@ struct Cli {
     output_path: Option<PathBuf>,
     #[arg(short, long)]
     deck_type: Option<DeckType>,
+    #[arg(short, long)]
+    profile: Option<PathBuf>,
+    #[command(flatten)]
+    extractor_args: ExtractorArgs,
 }
 
 fn main() -> eyre::Result<()> {
@@ -39,6 +47,7 @@ fn main() -> eyre::Result<()> {
     let deck = match cli.deck_type.unwrap_or_default() {
         DeckType::RulesTemplates => create_rules_template_deck(&cli, model_id, deck_id)?,
         DeckType::Interpretations => create_interpretations_deck(&cli, model_id, deck_id)?,
+        DeckType::StandardsTemplates => create_standards_template_deck(&cli, model_id, deck_id)?,
     };
 
     deck.write_to_file(
@@ -50,6 +59,7 @@ fn main() -> eyre::Result<()> {
                 match cli.deck_type.unwrap_or_default() {
                     DeckType::RulesTemplates => "Regeln",
                     DeckType::Interpretations => "Interpretationen",
+                    DeckType::StandardsTemplates => "Standards",
                 }
             )),
     )?;
@@ -57,8 +67,19 @@ fn main() -> eyre::Result<()> {
     Ok(())
 }
 
+fn rules_parser(cli: &Cli) -> eyre::Result<RulesParser> {
+    let profile = cli
+        .profile
+        .as_deref()
+        .map(EditionProfile::load)
+        .transpose()?
+        .unwrap_or_default();
+    Ok(RulesParser::new(profile))
+}
+
 fn create_interpretations_deck(cli: &Cli, model_id: i64, deck_id: i64) -> eyre::Result<Deck> {
-    let interpretations: Vec<_> = RulesParser::parse_interpretations(&cli.rules_path)?
+    let interpretations: Vec<_> = rules_parser(cli)?
+        .parse_interpretations(&cli.rules_path, cli.extractor_args.build().as_ref())?
         .into_values()
         .flatten()
         .collect();
@@ -108,7 +129,10 @@ fn create_interpretations_deck(cli: &Cli, model_id: i64, deck_id: i64) -> eyre::
 }
 
 fn create_rules_template_deck(cli: &Cli, model_id: i64, deck_id: i64) -> eyre::Result<Deck> {
-    let interpretations: Vec<_> = RulesParser::parse(&cli.rules_path)?.into_values().collect();
+    let interpretations: Vec<_> = rules_parser(cli)?
+        .parse(&cli.rules_path, cli.extractor_args.build().as_ref())?
+        .into_values()
+        .collect();
     let mut deck = Deck::new(
         deck_id,
         &format!("American Football in Deutschland: Regeln ({})", cli.year),
@@ -152,6 +176,58 @@ fn create_rules_template_deck(cli: &Cli, model_id: i64, deck_id: i64) -> eyre::R
     Ok(deck)
 }
 
+fn create_standards_template_deck(cli: &Cli, model_id: i64, deck_id: i64) -> eyre::Result<Deck> {
+    let standards: Vec<_> = rules_parser(cli)?
+        .parse_standards(&cli.rules_path, cli.extractor_args.build().as_ref())?
+        .into_iter()
+        .flat_map(|s| s.standards)
+        .collect();
+    let mut deck = Deck::new(
+        deck_id,
+        &format!(
+            "American Football in Deutschland: Standards für Schiedsrichter ({})",
+            cli.year
+        ),
+        &format!(
+            "Standards für Schiedsrichter aus dem AFVD American Football Regelwerk von {}",
+            cli.year
+        ),
+    );
+
+    let model = Model::new(
+        model_id,
+        "Standard",
+        vec![
+            Field::new("Standard"),
+            Field::new("Text"),
+            Field::new("Back Extra"),
+        ],
+        vec![
+            Template::new("Standard")
+                .qfmt("{{cloze:Text}}")
+                .afmt("{{cloze:Text}}<br>{{Back Extra}}"),
+        ],
+    )
+    .model_type(genanki_rs::ModelType::Cloze)
+    .css(CSS);
+
+    for standard in standards {
+        deck.add_note(
+            Note::new(
+                model.clone(),
+                vec![
+                    &standard.to_title(),
+                    &render_standard(&standard).into_string(),
+                    &render_standard_back_extra(cli).into_string(),
+                ],
+            )?
+            .tags(standard_nr_to_tags(standard.standard_nr)),
+        );
+    }
+
+    Ok(deck)
+}
+
 fn render_rule(rule: &Rule) -> Markup {
     html! {
         div.header {
@@ -174,6 +250,27 @@ fn render_back_extra(rule: &Rule, cli: &Cli) -> Markup {
     }
 }
 
+fn render_standard(standard: &Standard) -> Markup {
+    html! {
+        div.header {
+            (standard.to_title())
+        }
+        div.rule {
+            (standard.text)
+        }
+    }
+}
+
+fn render_standard_back_extra(cli: &Cli) -> Markup {
+    html! {
+        div.link {
+            a href=(cli.rules_url) target="_blank" rel="noreferrer noopener" {
+                "Offizielles Regelwerk"
+            }
+        }
+    }
+}
+
 fn render_interpretation(interpretation: &RuleInterpretation) -> Markup {
     html! {
         div.header {
@@ -212,12 +309,23 @@ fn article_nr_to_tags(article_nr: ArticleNr) -> [String; 2] {
     ]
 }
 
+fn standard_nr_to_tags(standard_nr: StandardNr) -> [String; 2] {
+    [
+        "AmericanFootball::Standards".to_string(),
+        format!(
+            "AmericanFootball::Standards::Abschnitt{}.{}",
+            standard_nr.0, standard_nr.1
+        ),
+    ]
+}
+
 fn get_model_and_deck_ids(year: u16, deck_type: DeckType) -> eyre::Result<(i64, i64)> {
     const VERSION: &str = env!("CARGO_PKG_VERSION");
     const BASE_ID: i64 = 63754;
     let deck_type_offset = match deck_type {
         DeckType::RulesTemplates => 2,
         DeckType::Interpretations => 0,
+        DeckType::StandardsTemplates => 4,
     };
     let id_base_deck = (BASE_ID + deck_type_offset) * 10_000_000_000_000;
     let id_base_model = (BASE_ID + deck_type_offset + 1) * 10_000_000_000_000;
@@ -0,0 +1,132 @@
+use maud::{Markup, Render, html};
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use crate::reference::render_with_links;
+
+/// Block of the structured text of a rule
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, ToSchema)]
+pub enum RuleBlock {
+    Paragraph(String),
+    Penalty(String),
+    List(RuleList),
+    Exceptions {
+        text: String,
+        #[schema(no_recursion)]
+        blocks: Vec<RuleBlock>,
+    },
+    Note {
+        title: String,
+        #[schema(no_recursion)]
+        blocks: Vec<RuleBlock>,
+    },
+}
+
+impl RuleBlock {
+    /// Listing item with the given path like ["a", "4"] inside this block
+    pub fn find_item(&self, item_path: &[String]) -> Option<&ListItem> {
+        match self {
+            RuleBlock::List(list) => list.items.iter().find_map(|item| {
+                if item.item_path == item_path {
+                    Some(item)
+                } else if item_path.starts_with(&item.item_path) {
+                    item.blocks.iter().find_map(|b| b.find_item(item_path))
+                } else {
+                    None
+                }
+            }),
+            RuleBlock::Exceptions { blocks, .. } | RuleBlock::Note { blocks, .. } => {
+                blocks.iter().find_map(|b| b.find_item(item_path))
+            }
+            RuleBlock::Paragraph(_) | RuleBlock::Penalty(_) => None,
+        }
+    }
+}
+
+impl Render for RuleBlock {
+    fn render(&self) -> Markup {
+        match self {
+            RuleBlock::Paragraph(text) => html! { p { (render_with_links(text)) } },
+            RuleBlock::Penalty(text) => html! { p { strong { (render_with_links(text)) } } },
+            RuleBlock::List(list) => html! { (list) },
+            RuleBlock::Exceptions { text, blocks } => html! {
+                p { strong { (text) } }
+                @for block in blocks {
+                    (block)
+                }
+            },
+            RuleBlock::Note { title, blocks } => html! {
+                p { (title) }
+                @for block in blocks {
+                    (block)
+                }
+            },
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq, Serialize, ToSchema)]
+pub enum ListStyle {
+    Alphabetic,
+    Numeric,
+}
+
+impl ListStyle {
+    /// Style of a listing by its nesting depth like "a)", "1." and "a."
+    pub fn from_depth(depth: usize) -> Self {
+        match depth {
+            2 => ListStyle::Numeric,
+            _ => ListStyle::Alphabetic,
+        }
+    }
+
+    pub fn to_html_type(self) -> &'static str {
+        match self {
+            ListStyle::Alphabetic => "a",
+            ListStyle::Numeric => "1",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, ToSchema)]
+pub struct RuleList {
+    pub style: ListStyle,
+    pub items: Vec<ListItem>,
+}
+
+impl Render for RuleList {
+    fn render(&self) -> Markup {
+        html! {
+            ol type=(self.style.to_html_type()) {
+                @for item in &self.items {
+                    (item)
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, ToSchema)]
+pub struct ListItem {
+    pub item_path: Vec<String>,
+    pub text: String,
+    #[schema(no_recursion)]
+    pub blocks: Vec<RuleBlock>,
+}
+
+impl Render for ListItem {
+    fn render(&self) -> Markup {
+        html! {
+            li id=(self.item_path.join("-")) {
+                @if matches!(self.blocks.first(), Some(RuleBlock::Penalty(_))) {
+                    p { (render_with_links(&self.text)) }
+                } @else {
+                    (render_with_links(&self.text))
+                }
+                @for block in &self.blocks {
+                    (block)
+                }
+            }
+        }
+    }
+}
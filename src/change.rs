@@ -0,0 +1,81 @@
+use serde::Serialize;
+use std::fmt::Display;
+
+use crate::rule::{ArticleNr, InterpretationRef, RuleRef};
+
+/// Changes of the current season that are listed at the beginning of part I and III
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct SeasonChanges {
+    pub rules: Vec<RuleChange>,
+    pub interpretations: Vec<InterpretationChange>,
+}
+
+impl SeasonChanges {
+    pub fn is_rule_changed(&self, article_nr: ArticleNr) -> bool {
+        self.rules
+            .iter()
+            .any(|c| c.rule_ref.article_nr == article_nr)
+    }
+
+    pub fn is_interpretation_changed(&self, article_nr: ArticleNr, index: u8) -> bool {
+        self.interpretations
+            .iter()
+            .any(|c| c.article_nr == article_nr && c.index == index)
+    }
+}
+
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq, Serialize)]
+pub enum RuleChangeKind {
+    Rule,
+    Editorial,
+}
+
+/// Entry of a change list like "7.1.5.a.4" or "9.2.5.b Strafe"
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize)]
+pub struct RuleChange {
+    pub kind: RuleChangeKind,
+    pub rule_ref: RuleRef,
+    pub remark: Option<String>,
+    pub description: String,
+}
+
+impl RuleChange {
+    pub fn reference(&self) -> String {
+        match &self.remark {
+            Some(remark) => format!("{} {}", self.rule_ref, remark),
+            None => self.rule_ref.to_string(),
+        }
+    }
+}
+
+impl Display for RuleChange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}", self.reference(), self.description)
+    }
+}
+
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq, Serialize)]
+pub enum InterpretationChangeKind {
+    New,
+    Changed,
+}
+
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize)]
+pub struct InterpretationChange {
+    pub kind: InterpretationChangeKind,
+    pub article_nr: ArticleNr,
+    pub index: u8,
+}
+
+impl InterpretationChange {
+    pub fn get_title(&self) -> String {
+        self.to_ref().to_string()
+    }
+
+    pub fn to_ref(&self) -> InterpretationRef {
+        InterpretationRef {
+            article_nr: self.article_nr,
+            index: self.index,
+        }
+    }
+}
@@ -0,0 +1,106 @@
+use indexmap::IndexMap;
+use maud::{Markup, html};
+use serde::Serialize;
+
+use crate::rule::{ArticleNr, Rule, SectionNr};
+
+/// Chapter ("Regel") of the rules in part I of the rule book
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize)]
+pub struct Chapter {
+    pub chapter_nr: u8,
+    pub title: String,
+    pub sections: Vec<Section>,
+}
+
+impl Chapter {
+    pub fn to_title(&self) -> String {
+        format!("Regel {} {}", self.chapter_nr, self.title)
+    }
+
+    pub fn to_url(&self, base_url: &str) -> String {
+        format!("{}/chapter/{}", base_url, self.chapter_nr)
+    }
+
+    pub fn render_contents(&self, rules: &IndexMap<ArticleNr, Rule>) -> Markup {
+        html! {
+            ul {
+                @for section in &self.sections {
+                    li {
+                        a href=(section.to_url("")) { (section.to_title()) }
+                        (section.render_contents(rules))
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize)]
+pub struct Section {
+    pub section_nr: SectionNr,
+    pub title: String,
+}
+
+impl Section {
+    pub fn to_title(&self) -> String {
+        format!("Abschnitt {} {}", self.section_nr, self.title)
+    }
+
+    pub fn to_url(&self, base_url: &str) -> String {
+        format!(
+            "{}/section/{}",
+            base_url,
+            self.section_nr.to_path_parameter()
+        )
+    }
+
+    pub fn rules<'a>(
+        &self,
+        rules: &'a IndexMap<ArticleNr, Rule>,
+    ) -> impl Iterator<Item = &'a Rule> + use<'a> {
+        let section_nr = self.section_nr;
+        rules
+            .values()
+            .filter(move |r| r.article_nr.section_nr() == section_nr)
+    }
+
+    pub fn render_contents(&self, rules: &IndexMap<ArticleNr, Rule>) -> Markup {
+        html! {
+            ul {
+                @for rule in self.rules(rules) {
+                    li { a href=(rule.to_url("")) { (rule.to_title()) } }
+                }
+            }
+        }
+    }
+}
+
+/// Collapsible list of all chapters with their sections and articles
+pub fn render_table_of_contents(chapters: &[Chapter], rules: &IndexMap<ArticleNr, Rule>) -> Markup {
+    html! {
+        article.message ."is-size-5" {
+            div.message-header {
+                p { "Inhaltsverzeichnis" }
+            }
+            div.message-body {
+                .content {
+                    @for chapter in chapters {
+                        details {
+                            summary { b { (chapter.to_title()) } }
+                            p { a href=(chapter.to_url("")) { "Ganze Regel" } }
+                            (chapter.render_contents(rules))
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Chapter and section that contain the given section
+pub fn find_section(chapters: &[Chapter], section_nr: SectionNr) -> Option<(&Chapter, &Section)> {
+    chapters
+        .iter()
+        .filter(|c| c.chapter_nr == section_nr.0)
+        .find_map(|c| Some((c, c.sections.iter().find(|s| s.section_nr == section_nr)?)))
+}
@@ -1,4 +1,4 @@
-use crate::rule::Rule;
+use crate::rotation::RotationContent;
 use crate::{AppState, get_current_datetime};
 use crate::{OPENGRAPH_PNG, PUB_URL};
 
@@ -13,13 +13,20 @@ use std::sync::{
     atomic::{AtomicBool, Ordering},
 };
 use tokio::time;
-use tracing::{error, info};
+use tracing::{error, info, warn};
 
 pub struct DiscordEventHandler {
     pub is_loop_running: AtomicBool,
     pub app_state: Arc<AppState>,
-    pub discord_post_hour: u8,
-    pub discord_channel_id: u64,
+    pub schedules: Vec<DiscordSchedule>,
+}
+
+/// Daily post of the current item of a rotation
+#[derive(Debug, Clone)]
+pub struct DiscordSchedule {
+    pub rotation: String,
+    pub channel_id: u64,
+    pub post_hour: u8,
 }
 
 #[serenity::async_trait]
@@ -32,36 +39,58 @@ impl EventHandler for DiscordEventHandler {
         info!("Cache built successfully!");
 
         if !self.is_loop_running.load(Ordering::Relaxed) {
-            let discord_post_hour = self.discord_post_hour;
-            let discord_channel_id = self.discord_channel_id;
             let app_state = self.app_state.clone();
+            let schedules = self.schedules.clone();
             tokio::spawn(async move {
                 let mut interval = time::interval(time::Duration::from_secs(30));
-                let mut last_send_date = get_current_datetime()
-                    .checked_sub(1.days())
-                    .expect("Could not subtract day from current time")
-                    .date();
+                let mut last_send_dates = vec![
+                    get_current_datetime()
+                        .checked_sub(1.days())
+                        .expect("Could not subtract day from current time")
+                        .date();
+                    schedules.len()
+                ];
                 loop {
                     interval.tick().await;
                     let current_date_time = get_current_datetime();
 
-                    if current_date_time.hour() as u8 == discord_post_hour
-                        && current_date_time.minute() == 0
-                        && current_date_time.date() > last_send_date
-                    {
-                        info!("Send message");
+                    for (schedule, last_send_date) in schedules.iter().zip(&mut last_send_dates) {
+                        if current_date_time.hour() as u8 != schedule.post_hour
+                            || current_date_time.minute() != 0
+                            || current_date_time.date() <= *last_send_date
+                        {
+                            continue;
+                        }
+                        info!("Send message of rotation {}", schedule.rotation);
 
                         let message = {
                             let dynamic_state = app_state.dynamic_state.read().unwrap();
-                            dynamic_state.discord_message.clone()
+                            if dynamic_state.current_date != current_date_time.date() {
+                                // The state of the new day is not updated yet
+                                continue;
+                            }
+                            let rotation_state = &dynamic_state.rotations[&schedule.rotation];
+                            if !rotation_state.schedule.is_new_on(current_date_time.date()) {
+                                // Rotations with longer intervals keep their item for several days
+                                *last_send_date = current_date_time.date();
+                                continue;
+                            }
+                            rotation_state.discord_message.clone()
+                        };
+                        *last_send_date = current_date_time.date();
+                        let Some(message) = message else {
+                            warn!(
+                                "Current item of rotation {} is not available",
+                                schedule.rotation
+                            );
+                            continue;
                         };
-                        if let Err(err) = ChannelId::new(discord_channel_id)
+                        if let Err(err) = ChannelId::new(schedule.channel_id)
                             .send_message(&ctx, message)
                             .await
                         {
                             error!("Could not send message: {err}");
                         }
-                        last_send_date = current_date_time.date();
                     }
                 }
             });
@@ -72,11 +101,11 @@ impl EventHandler for DiscordEventHandler {
     }
 }
 
-pub fn build_discord_message(rule: &Rule) -> CreateMessage {
+pub fn build_discord_message(content: &RotationContent) -> CreateMessage {
     let embed = CreateEmbed::new()
-        .title(rule.to_title())
-        .url(rule.to_url(PUB_URL))
+        .title(content.to_title())
+        .url(content.to_url(PUB_URL))
         .image(format!("{PUB_URL}{OPENGRAPH_PNG}"))
-        .description(rule.to_description());
+        .description(content.to_description());
     CreateMessage::new().embed(embed)
 }
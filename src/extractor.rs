@@ -0,0 +1,413 @@
+use clap::{Args, ValueEnum};
+use eyre::{Context, eyre};
+use pdf_extract::{Document, MediaBox, OutputDev, OutputError, Transform, output_doc_page};
+use std::{fmt::Write, path::Path, process::Command, str::FromStr};
+
+pub trait TextExtractor {
+    fn extract_text(&self, rules_path: &Path) -> eyre::Result<String>;
+}
+
+#[derive(Debug, Copy, Clone, Default, ValueEnum)]
+pub enum TextExtractorKind {
+    #[default]
+    Pdftotext,
+    Builtin,
+}
+
+impl TextExtractorKind {
+    pub fn build(self, options: ExtractionOptions) -> Box<dyn TextExtractor + Send + Sync> {
+        match self {
+            TextExtractorKind::Pdftotext => Box::new(Pdftotext { options }),
+            TextExtractorKind::Builtin => Box::new(BuiltinExtractor { options }),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Args)]
+pub struct ExtractorArgs {
+    /// pdftotext stays the default because it produced the text the parser is tested with, the
+    /// builtin extractor is only compared with it by the online tests
+    #[arg(long, value_enum, default_value_t = TextExtractorKind::Pdftotext)]
+    pub text_extractor: TextExtractorKind,
+    /// Crop region as x,y,width,height in PDF points
+    #[arg(long)]
+    pub crop: Option<CropRegion>,
+    /// First page to extract, starting at 1
+    #[arg(long, value_parser = clap::value_parser!(u32).range(1..))]
+    pub first_page: Option<u32>,
+    /// Last page to extract
+    #[arg(long, value_parser = clap::value_parser!(u32).range(1..))]
+    pub last_page: Option<u32>,
+}
+
+impl ExtractorArgs {
+    pub fn build(&self) -> Box<dyn TextExtractor + Send + Sync> {
+        let default_options = ExtractionOptions::default();
+        self.text_extractor.build(ExtractionOptions {
+            crop: self.crop.or(default_options.crop),
+            first_page: self.first_page,
+            last_page: self.last_page,
+        })
+    }
+}
+
+/// Region of a page in PDF points with the origin at the top left corner.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct CropRegion {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+impl CropRegion {
+    fn contains(&self, x: f64, y: f64) -> bool {
+        x >= self.x && x <= self.x + self.width && y >= self.y && y <= self.y + self.height
+    }
+}
+
+impl FromStr for CropRegion {
+    type Err = eyre::Report;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts = s
+            .split(',')
+            .map(|p| p.trim().parse())
+            .collect::<Result<Vec<f64>, _>>()
+            .map_err(|_| eyre!("Parts have to be numbers"))?;
+        if let [x, y, width, height] = &parts[..] {
+            Ok(Self {
+                x: *x,
+                y: *y,
+                width: *width,
+                height: *height,
+            })
+        } else {
+            Err(eyre!("Invalid crop region: {s}"))
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExtractionOptions {
+    pub crop: Option<CropRegion>,
+    pub first_page: Option<u32>,
+    pub last_page: Option<u32>,
+}
+
+impl ExtractionOptions {
+    /// Pages are counted from 1 like in pdftotext
+    fn validate_page_range(&self) -> eyre::Result<()> {
+        match (self.first_page, self.last_page) {
+            (Some(0), _) | (_, Some(0)) => Err(eyre!("Pages are counted from 1")),
+            (Some(first_page), Some(last_page)) if first_page > last_page => Err(eyre!(
+                "First page {first_page} is after last page {last_page}"
+            )),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl Default for ExtractionOptions {
+    fn default() -> Self {
+        // Cuts off page headers and footers of the rule book
+        Self {
+            crop: Some(CropRegion {
+                x: 0.,
+                y: 40.,
+                width: 1000.,
+                height: 540.,
+            }),
+            first_page: None,
+            last_page: None,
+        }
+    }
+}
+
+pub struct Pdftotext {
+    pub options: ExtractionOptions,
+}
+
+impl TextExtractor for Pdftotext {
+    fn extract_text(&self, rules_path: &Path) -> eyre::Result<String> {
+        self.options.validate_page_range()?;
+        let mut command = Command::new("pdftotext");
+        if let Some(crop) = self.options.crop {
+            command.args([
+                "-x",
+                &crop.x.to_string(),
+                "-y",
+                &crop.y.to_string(),
+                "-W",
+                &crop.width.to_string(),
+                "-H",
+                &crop.height.to_string(),
+            ]);
+        }
+        if let Some(first_page) = self.options.first_page {
+            command.args(["-f", &first_page.to_string()]);
+        }
+        if let Some(last_page) = self.options.last_page {
+            command.args(["-l", &last_page.to_string()]);
+        }
+        let pdftotext_output = command
+            .args([
+                rules_path
+                    .to_str()
+                    .ok_or(eyre!("Invalid path to rules pdf"))?,
+                "-",
+            ])
+            .output()
+            .wrap_err("Could not run pdftotext")?;
+        if !pdftotext_output.status.success() {
+            return Err(eyre!(
+                "pdftotext did not exit successfuly: {}",
+                String::from_utf8(pdftotext_output.stderr)?
+            ));
+        }
+
+        String::from_utf8(pdftotext_output.stdout).wrap_err("Stdout is no valid utf8")
+    }
+}
+
+/// In-process extraction that mimics the raw output of pdftotext.
+pub struct BuiltinExtractor {
+    pub options: ExtractionOptions,
+}
+
+impl TextExtractor for BuiltinExtractor {
+    fn extract_text(&self, rules_path: &Path) -> eyre::Result<String> {
+        self.options.validate_page_range()?;
+        let document = Document::load(rules_path).wrap_err("Could not load rules pdf")?;
+        let pages = document.get_pages();
+        let first_page = self.options.first_page.unwrap_or(1);
+        let last_page = self
+            .options
+            .last_page
+            .unwrap_or(pages.len() as u32)
+            .min(pages.len() as u32);
+        if first_page > last_page {
+            return Err(eyre!(
+                "First page {first_page} is after the last page {last_page} of the rules pdf"
+            ));
+        }
+
+        let mut output = CroppedTextOutput::new(self.options.crop);
+        for page_num in first_page..=last_page {
+            output_doc_page(&document, &mut output, page_num)
+                .map_err(|err| eyre!("Could not extract text from page {page_num}: {err:?}"))?;
+        }
+
+        Ok(replace_ligatures(&output.text))
+    }
+}
+
+fn replace_ligatures(text: &str) -> String {
+    text.replace('ﬀ', "ff")
+        .replace('ﬁ', "fi")
+        .replace('ﬂ', "fl")
+        .replace('ﬃ', "ffi")
+        .replace('ﬄ', "ffl")
+}
+
+struct CroppedTextOutput {
+    crop: Option<CropRegion>,
+    text: String,
+    page_height: f64,
+    last_end: f64,
+    last_y: f64,
+    first_char: bool,
+    page_has_text: bool,
+}
+
+impl CroppedTextOutput {
+    fn new(crop: Option<CropRegion>) -> Self {
+        Self {
+            crop,
+            text: String::new(),
+            page_height: 0.,
+            last_end: f64::MAX,
+            last_y: 0.,
+            first_char: false,
+            page_has_text: false,
+        }
+    }
+}
+
+impl OutputDev for CroppedTextOutput {
+    fn begin_page(
+        &mut self,
+        _page_num: u32,
+        media_box: &MediaBox,
+        _art_box: Option<(f64, f64, f64, f64)>,
+    ) -> Result<(), OutputError> {
+        self.page_height = media_box.ury - media_box.lly;
+        self.last_end = f64::MAX;
+        self.page_has_text = false;
+        Ok(())
+    }
+
+    fn end_page(&mut self) -> Result<(), OutputError> {
+        // pdftotext terminates every page with a form feed
+        if self.page_has_text {
+            self.text.push('\n');
+        }
+        self.text.push('\x0C');
+        Ok(())
+    }
+
+    fn output_character(
+        &mut self,
+        trm: &Transform,
+        width: f64,
+        _spacing: f64,
+        font_size: f64,
+        char: &str,
+    ) -> Result<(), OutputError> {
+        let x = trm.m31;
+        let y = self.page_height - trm.m32;
+        if let Some(crop) = self.crop
+            && !crop.contains(x, y)
+        {
+            return Ok(());
+        }
+
+        let scale_x = font_size * (trm.m11 + trm.m21);
+        let scale_y = font_size * (trm.m12 + trm.m22);
+        let transformed_font_size = (scale_x * scale_y).abs().sqrt();
+
+        if self.page_has_text && self.first_char {
+            let line_distance = (y - self.last_y).abs();
+            if line_distance > transformed_font_size * 1.5 {
+                self.text.push('\n');
+            }
+            if x < self.last_end && line_distance > transformed_font_size * 0.5 {
+                self.text.push('\n');
+            } else if x > self.last_end + transformed_font_size * 0.1 {
+                self.text.push(' ');
+            }
+        }
+        write!(self.text, "{char}")?;
+
+        self.first_char = false;
+        self.page_has_text = true;
+        self.last_y = y;
+        self.last_end = x + width * transformed_font_size;
+        Ok(())
+    }
+
+    fn begin_word(&mut self) -> Result<(), OutputError> {
+        self.first_char = true;
+        Ok(())
+    }
+
+    fn end_word(&mut self) -> Result<(), OutputError> {
+        Ok(())
+    }
+
+    fn end_line(&mut self) -> Result<(), OutputError> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_crop_region_from_str() {
+        assert_eq!(
+            "0,40, 1000.5,540".parse::<CropRegion>().unwrap(),
+            CropRegion {
+                x: 0.,
+                y: 40.,
+                width: 1000.5,
+                height: 540.,
+            }
+        );
+        assert!("0,40,1000".parse::<CropRegion>().is_err());
+        assert!("0,40,1000,540,1".parse::<CropRegion>().is_err());
+        assert!("0,top,1000,540".parse::<CropRegion>().is_err());
+    }
+
+    #[test]
+    fn test_page_range() {
+        let options = |first_page, last_page| ExtractionOptions {
+            first_page,
+            last_page,
+            ..Default::default()
+        };
+        assert!(options(None, None).validate_page_range().is_ok());
+        assert!(options(Some(1), Some(1)).validate_page_range().is_ok());
+        assert!(options(Some(3), None).validate_page_range().is_ok());
+        assert!(options(Some(0), None).validate_page_range().is_err());
+        assert!(options(None, Some(0)).validate_page_range().is_err());
+        assert!(options(Some(5), Some(4)).validate_page_range().is_err());
+
+        let extractor = BuiltinExtractor {
+            options: options(Some(0), None),
+        };
+        assert!(extractor.extract_text(Path::new("missing.pdf")).is_err());
+    }
+
+    #[test]
+    fn test_builtin_extractor_offline() {
+        // Two pages with a header and a page number outside of the default crop region
+        let rules_path = Path::new("tests/fixtures/extractor.pdf");
+        let extractor = BuiltinExtractor {
+            options: ExtractionOptions::default(),
+        };
+        assert_eq!(
+            extractor.extract_text(rules_path).unwrap(),
+            concat!(
+                "Regel 1\nDas Spiel, das Spielfeld und der Ball\n\nAbschnitt 1 Allgemeine Bestimmungen\n\x0C",
+                "Artikel 1\nDas Spiel wird von zwei Teams gespielt.\nJedes Team hat höchstens elf Spieler.\n\x0C"
+            )
+        );
+
+        let extractor = BuiltinExtractor {
+            options: ExtractionOptions {
+                crop: None,
+                first_page: Some(2),
+                last_page: Some(5),
+            },
+        };
+        assert_eq!(
+            extractor.extract_text(rules_path).unwrap(),
+            "Football Regelbuch 2026\n\nArtikel 1\nDas Spiel wird von zwei Teams gespielt.\nJedes Team hat höchstens elf Spieler.\n\n6\n\x0C"
+        );
+    }
+
+    // Run with `cargo test --features online-tests` to compare with the output of pdftotext
+    #[cfg(feature = "online-tests")]
+    #[test]
+    fn test_builtin_extractor() {
+        use crate::{RULE_BOOK_URL, parser::RulesParser};
+        use std::path::PathBuf;
+        use temp_testdir::TempDir;
+
+        let temp = TempDir::default();
+
+        let mut rules_path = PathBuf::from(temp.as_ref());
+        rules_path.push("rules.pdf");
+
+        let rules_response = reqwest::blocking::get(RULE_BOOK_URL)
+            .unwrap()
+            .error_for_status()
+            .unwrap();
+
+        std::fs::write(&rules_path, rules_response.bytes().unwrap()).unwrap();
+
+        let extractor = BuiltinExtractor {
+            options: ExtractionOptions::default(),
+        };
+        let rules_text = extractor.extract_text(&rules_path).unwrap();
+        let parser = RulesParser::default();
+        assert_eq!(
+            parser.preprocess_text(rules_text),
+            parser.preprocess_text(
+                include_str!("../tests/fixtures/Football_Regelbuch_2026.txt").to_string()
+            )
+        );
+    }
+}
@@ -1,15 +1,34 @@
 use std::sync::RwLock;
 
+use change::SeasonChanges;
+use chapter::Chapter;
 use indexmap::IndexMap;
 use jiff::{Timestamp, Zoned, civil::Date, tz::TimeZone};
 use maud::Markup;
+use rotation::{RotationConfig, Schedule};
 use rule::{ArticleNr, Rule};
+use search::SearchIndex;
 use serenity::all::CreateMessage;
 use shadow_rs::shadow;
+use signal::Signal;
+use standard::StandardSection;
 
+pub mod body;
+pub mod change;
+pub mod chapter;
 pub mod discord;
+pub mod extractor;
 pub mod parser;
+pub mod penalty;
+pub mod profile;
+pub mod quiz;
+pub mod reference;
+pub mod rotation;
 pub mod rule;
+pub mod search;
+pub mod selection;
+pub mod signal;
+pub mod standard;
 
 shadow!(build);
 
@@ -21,18 +40,37 @@ pub const RULE_BOOK_URL: &str =
 
 pub struct AppState {
     pub rules: IndexMap<ArticleNr, Rule>,
+    pub chapters: Vec<Chapter>,
+    pub signals: IndexMap<u8, Signal>,
+    pub standards: Vec<StandardSection>,
+    pub changes: SeasonChanges,
+    pub search_index: SearchIndex,
     pub start_date: Date,
-    pub rule_order: Vec<usize>,
+    pub allow_future_days: bool,
+    pub rotations: IndexMap<String, RotationConfig>,
     pub dynamic_state: RwLock<DynamicState>,
 }
 
 pub struct DynamicState {
     pub current_date: Date,
-    pub current_rule_markup: Markup,
+    pub rotations: IndexMap<String, RotationState>,
+}
+
+pub struct RotationState {
+    pub schedule: Schedule,
+    pub current_markup: Markup,
     pub rss: String,
-    pub discord_message: CreateMessage,
+    pub atom: String,
+    pub json_feed: String,
+    /// Nothing is posted while the current item is not available
+    pub discord_message: Option<CreateMessage>,
 }
 
 pub fn get_current_datetime() -> Zoned {
     Timestamp::now().to_zoned(TimeZone::get("Europe/Berlin").expect("Could not get timezone"))
 }
+
+pub fn get_start_of_day(date: Date) -> Zoned {
+    date.to_zoned(TimeZone::get("Europe/Berlin").expect("Could not get timezone"))
+        .expect("Could not get start of day")
+}
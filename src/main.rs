@@ -1,22 +1,25 @@
+use atom_syndication::{ContentBuilder, EntryBuilder, FeedBuilder, FixedDateTime, LinkBuilder};
 use axum::{
-    Router,
-    extract::{Path, State},
+    Form, Json, Router,
+    extract::{Path, Query, State},
     http::{
-        StatusCode,
+        HeaderMap, StatusCode,
         header::{self, HeaderValue},
     },
-    routing::get,
+    response::{IntoResponse, Redirect, Response},
+    routing::{get, post},
 };
 use axum_extra::{TypedHeader, headers::ContentType};
 use clap::{Args, Parser};
 use eyre::eyre;
 use indexmap::IndexMap;
-use jiff::{civil::Date, fmt::rfc2822};
+use jiff::{Span, Timestamp, civil::Date, fmt::rfc2822};
 use maud::{DOCTYPE, Markup, Render, html};
-use rand::{RngExt, rng, seq::SliceRandom};
+use rand::{RngExt, rng, seq::IndexedRandom};
 use rand_pcg::Pcg64;
 use rand_seeder::Seeder;
-use rss::{ChannelBuilder, ItemBuilder};
+use rss::{ChannelBuilder, GuidBuilder, ItemBuilder};
+use serde::{Deserialize, Serialize};
 use serenity::{Client, all::GatewayIntents};
 use std::{
     path::PathBuf,
@@ -25,19 +28,31 @@ use std::{
 use tokio::time;
 use tower::ServiceBuilder;
 use tower_http::{services::ServeDir, set_header::SetResponseHeaderLayer, trace::TraceLayer};
-use tracing::{debug, error, info, warn};
+use tracing::{error, info, warn};
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
+use utoipa::{IntoParams, OpenApi, ToSchema};
 use veil::Redact;
 
 use afrotd::{
-    AppState, DynamicState, OPENGRAPH_PNG, PUB_URL, RSS_SVG, RULE_BOOK_URL,
+    AppState, DynamicState, OPENGRAPH_PNG, PUB_URL, RSS_SVG, RULE_BOOK_URL, RotationState,
     build::{PKG_VERSION, SHORT_COMMIT},
-    discord::{DiscordEventHandler, build_discord_message},
-    get_current_datetime,
+    discord::{DiscordEventHandler, DiscordSchedule, build_discord_message},
+    extractor::ExtractorArgs,
+    get_current_datetime, get_start_of_day,
+    profile::EditionProfile,
+    rotation::{
+        DEFAULT_ROTATION, INTERPRETATION_ROTATION, RotationConfig, RotationContent, RotationPool,
+        Rotations, Schedule, ScheduleEntry,
+    },
 };
 use afrotd::{
+    change::{InterpretationChangeKind, RuleChangeKind},
+    chapter::{find_section, render_table_of_contents},
     parser,
-    rule::{ArticleNr, Rule},
+    quiz::{QuizMode, QuizQuestion, QuizScore},
+    reference::render_with_links,
+    rule::{ArticleNr, InterpretationRef, Rule, RuleInterpretation, RuleRef, SectionNr},
+    search::{SearchIndex, SearchResult},
 };
 
 #[derive(Debug, Clone, Parser)]
@@ -47,6 +62,21 @@ struct Cli {
     exclude_rule: Vec<ArticleNr>,
     #[arg(short, long)]
     start_date: Date,
+    #[arg(short, long)]
+    profile: Option<PathBuf>,
+    /// Allow looking up the rules of future days on the site, in the API and in the calendar
+    #[arg(long)]
+    allow_future_days: bool,
+    /// File that keeps the history of the published rules of the day
+    ///
+    /// The histories of other rotations are kept in the same directory unless configured otherwise
+    #[arg(long, default_value = "schedule.jsonl")]
+    schedule_path: PathBuf,
+    /// TOML file with the weighting of the rule of the day and additional rotations like rotations.example.toml
+    #[arg(short, long)]
+    rotations: Option<PathBuf>,
+    #[command(flatten)]
+    extractor_args: ExtractorArgs,
     #[command(flatten)]
     discord_args: DiscordArgs,
 }
@@ -63,6 +93,92 @@ struct DiscordArgs {
     discord_channel_id: Option<u64>,
 }
 
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+struct SearchParams {
+    /// Words that all have to be contained
+    q: Option<String>,
+}
+
+const SEARCH_LIMIT: usize = 50;
+
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+struct RandomParams {
+    /// Only rules of the chapter
+    chapter: Option<u8>,
+    /// Only rules with or without interpretations
+    with_interpretations: Option<bool>,
+    /// Only rules that were changed this season or not
+    changed: Option<bool>,
+    /// Seed for a reproducible pick
+    seed: Option<String>,
+}
+
+impl RandomParams {
+    fn matches(&self, rule: &Rule) -> bool {
+        self.chapter.is_none_or(|c| c == rule.article_nr.0)
+            && self
+                .with_interpretations
+                .is_none_or(|i| i != rule.interpretations.is_empty())
+            && self.changed.is_none_or(|c| c == rule.changed_this_season)
+    }
+}
+
+#[derive(Debug, Copy, Clone, Default, Deserialize)]
+struct QuizParams {
+    /// Only interpretations of the chapter
+    chapter: Option<u8>,
+    #[serde(default)]
+    mode: QuizMode,
+}
+
+impl QuizParams {
+    fn to_query(self) -> String {
+        let mut query = format!("mode={}", self.mode.to_query_value());
+        if let Some(chapter) = self.chapter {
+            query.push_str(&format!("&chapter={chapter}"));
+        }
+        query
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct QuizAnswer {
+    chapter: Option<u8>,
+    #[serde(default)]
+    mode: QuizMode,
+    /// Index of the chosen ruling in multiple choice mode
+    choice: Option<usize>,
+    /// Whether the ruling was known in self graded mode
+    correct: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ArchiveParams {
+    days: Option<u16>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CalendarParams {
+    days_before: Option<u16>,
+    days_after: Option<u16>,
+}
+
+const DESCRIPTION: &str = "Deine tägliche Dosis Regelwissen für American Football in Deutschland";
+const ARCHIVE_DAYS: u16 = 30;
+const MAX_ARCHIVE_DAYS: u16 = 365;
+const FEED_DAYS: usize = 14;
+const CALENDAR_DAYS_BEFORE: u16 = 7;
+const CALENDAR_DAYS_AFTER: u16 = 30;
+
+#[derive(Debug, Serialize, ToSchema)]
+struct DailyRule<'a> {
+    #[schema(value_type = String, format = Date)]
+    date: Date,
+    rule: &'a Rule,
+}
+
 #[tokio::main]
 async fn main() -> eyre::Result<()> {
     tracing_subscriber::registry()
@@ -85,35 +201,93 @@ async fn main() -> eyre::Result<()> {
         return Err(eyre!("Start date is later than current date!"));
     }
 
-    let mut rules = parser::RulesParser::parse(&cli.rules_path)?;
+    let profile = cli
+        .profile
+        .as_deref()
+        .map(EditionProfile::load)
+        .transpose()?
+        .unwrap_or_default();
+    let rules_parser = parser::RulesParser::new(profile);
+    let rules_text = cli.extractor_args.build().extract_text(&cli.rules_path)?;
+    let signals = rules_parser.extract_signals(&rules_text)?;
+    info!("Parsed {} signals", signals.len());
+    let standards = rules_parser.extract_standards(&rules_text)?;
+    info!("Parsed {} sections of standards", standards.len());
+    let chapters = rules_parser.extract_chapters(&rules_text)?;
+    info!("Parsed {} chapters", chapters.len());
+    let changes = rules_parser.extract_changes(&rules_text)?;
+    let mut rules = rules_parser.parse_text(rules_text)?;
     info!("Parsed {} rules", rules.len());
     for article_nr in &cli.exclude_rule {
         rules.shift_remove(article_nr);
     }
     info!("{} rules after exclusion", rules.len());
+    let search_index = SearchIndex::new(&rules);
 
-    let rule_order = {
-        let mut rng: Pcg64 = Seeder::from(&cli.start_date).into_rng();
-        let mut rule_order: Vec<_> = (0..(rules.len())).collect();
-        rule_order.shuffle(&mut rng);
-        rule_order
+    let additional_rotations = cli
+        .rotations
+        .as_deref()
+        .map(Rotations::load)
+        .transpose()?
+        .unwrap_or_default();
+    let default_rotation = RotationConfig {
+        name: DEFAULT_ROTATION.to_string(),
+        title: "Rule of the Day".to_string(),
+        pool: RotationPool::Rules,
+        chapters: vec![],
+        start_date: cli.start_date,
+        seed: None,
+        interval_days: 1,
+        weighting: additional_rotations.weighting,
+        schedule_path: Some(cli.schedule_path.clone()),
+        discord_channel_id: cli.discord_args.discord_channel_id,
+        discord_post_hour: cli.discord_args.discord_post_hour,
     };
-    debug!("Rule order: {:?}", rule_order);
+    // Without interpretations, e.g. after excluding rules, there is no interpretation of the day
+    let interpretation_rotation = rules
+        .values()
+        .any(|r| !r.interpretations.is_empty())
+        .then(|| RotationConfig::interpretation_of_the_day(cli.start_date));
+    let rotations: IndexMap<_, _> = std::iter::once(default_rotation)
+        .chain(interpretation_rotation)
+        .chain(additional_rotations.rotations)
+        .map(|r| (r.name.clone(), r))
+        .collect();
+    let mut rotation_states = IndexMap::new();
+    for rotation in rotations.values() {
+        let schedule = Schedule::load_for_rules(
+            Some(&rotation.get_schedule_path(&cli.schedule_path)),
+            rotation,
+            &rules,
+        )?;
+        let mut rotation_state = RotationState {
+            schedule,
+            current_markup: html! {},
+            rss: String::new(),
+            atom: String::new(),
+            json_feed: String::new(),
+            discord_message: None,
+        };
+        update_rotation_state(rotation, &mut rotation_state, &rules, current_date)?;
+        rotation_states.insert(rotation.name.clone(), rotation_state);
+    }
 
-    let current_rule = get_rule(cli.start_date, current_date, &rules, &rule_order).clone();
-    info!("Current rule: {}", current_rule.article_nr);
-    let current_rule_markup = html! { (current_rule) };
+    let dynamic_state = DynamicState {
+        current_date,
+        rotations: rotation_states,
+    };
 
     let state = Arc::new(AppState {
         rules,
+        chapters,
+        signals,
+        standards,
+        changes,
+        search_index,
         start_date: cli.start_date,
-        rule_order,
-        dynamic_state: RwLock::new(DynamicState {
-            current_date,
-            current_rule_markup,
-            rss: build_rss(&current_rule),
-            discord_message: build_discord_message(&current_rule),
-        }),
+        allow_future_days: cli.allow_future_days,
+        rotations,
+        dynamic_state: RwLock::new(dynamic_state),
     });
 
     // Task for updating the state when the date changes
@@ -127,28 +301,37 @@ async fn main() -> eyre::Result<()> {
             if current_date != state.dynamic_state.read().unwrap().current_date {
                 let mut dynamic_state = state.dynamic_state.write().unwrap();
                 dynamic_state.current_date = current_date;
-                let rule = get_rule(
-                    state.start_date,
-                    dynamic_state.current_date,
-                    &state.rules,
-                    &state.rule_order,
-                );
-                info!("New rule: {}", rule.article_nr);
-                dynamic_state.rss = build_rss(rule);
-                dynamic_state.discord_message = build_discord_message(rule);
-                dynamic_state.current_rule_markup = html! { (rule) };
+                for rotation in state.rotations.values() {
+                    let rotation_state = dynamic_state
+                        .rotations
+                        .get_mut(&rotation.name)
+                        .expect("Every rotation has a state");
+                    if let Err(err) =
+                        update_rotation_state(rotation, rotation_state, &state.rules, current_date)
+                    {
+                        error!("Could not update rotation {}: {err}", rotation.name);
+                    }
+                }
             }
         }
     });
 
     // Discord task
-    if let (Some(discord_token), Some(discord_post_hour), Some(discord_channel_id)) = (
-        cli.discord_args.discord_token,
-        cli.discord_args.discord_post_hour,
-        cli.discord_args.discord_channel_id,
-    ) {
+    if let Some(discord_token) = cli.discord_args.discord_token {
         info!("Init discord bot");
 
+        let schedules = state
+            .rotations
+            .values()
+            .filter_map(|r| {
+                Some(DiscordSchedule {
+                    rotation: r.name.clone(),
+                    channel_id: r.discord_channel_id?,
+                    post_hour: r.discord_post_hour?,
+                })
+            })
+            .collect();
+
         let task_state = state.clone();
         let intents = GatewayIntents::GUILDS;
 
@@ -156,8 +339,7 @@ async fn main() -> eyre::Result<()> {
             .event_handler(DiscordEventHandler {
                 is_loop_running: AtomicBool::new(false),
                 app_state: task_state,
-                discord_post_hour,
-                discord_channel_id,
+                schedules,
             })
             .await?;
 
@@ -169,12 +351,65 @@ async fn main() -> eyre::Result<()> {
         });
     }
 
-    let app = Router::new()
+    let app = build_router(state);
+
+    let listener = tokio::net::TcpListener::bind("0.0.0.0:3000").await.unwrap();
+    axum::serve(listener, app.into_make_service())
+        .await
+        .unwrap();
+    Ok(())
+}
+
+fn build_router(state: Arc<AppState>) -> Router {
+    // Pages that are also served as JSON depending on the Accept header
+    let negotiated = Router::new()
         .route("/", get(get_current_rule))
         .route("/all", get(get_all_rules))
-        .route("/random", get(get_random_rule))
+        .route("/day/{date}", get(get_day_rule))
         .route("/rule/{article_nr}", get(get_single_rule))
+        .route("/interpretations", get(get_all_interpretations))
+        .route("/interpretations/today", get(get_current_interpretation))
+        .route("/interpretations/random", get(get_random_interpretation))
+        .route(
+            "/interpretation/{article_nr}/{index}",
+            get(get_single_interpretation),
+        )
+        .route("/search", get(get_search))
+        .layer(SetResponseHeaderLayer::appending(
+            header::VARY,
+            HeaderValue::from_static("accept"),
+        ));
+
+    Router::new()
+        .merge(negotiated)
+        .route("/random", get(redirect_random_rule))
+        .route("/archive", get(get_archive))
+        .route("/chapter/{chapter_nr}", get(get_chapter))
+        .route("/section/{section_nr}", get(get_section))
+        .route("/signal/{signal_nr}", get(get_signal))
+        .route("/changes", get(get_changes))
+        .route("/standards", get(get_all_standards))
+        .route("/standards/{section_nr}", get(get_standard_section))
+        .route("/standards/{section_nr}/{index}", get(get_single_standard))
+        .route("/quiz", get(redirect_random_quiz))
+        .route("/quiz/reset", post(reset_quiz_score))
+        .route(
+            "/quiz/{article_nr}/{index}",
+            get(get_quiz_question).post(answer_quiz_question),
+        )
+        .route("/search.json", get(get_search_json))
         .route("/rss.xml", get(rss))
+        .route("/atom.xml", get(atom))
+        .route("/feed.json", get(json_feed))
+        .route("/rotations", get(get_rotations))
+        .route("/rotation/{name}", get(get_rotation))
+        .route("/rotation/{name}/day/{date}", get(get_rotation_day))
+        .route("/rotation/{name}/rss.xml", get(rotation_rss))
+        .route("/rotation/{name}/atom.xml", get(rotation_atom))
+        .route("/rotation/{name}/feed.json", get(rotation_json_feed))
+        .route("/calendar.ics", get(calendar))
+        .route("/api/openapi.json", get(Json(ApiDoc::openapi())))
+        .nest("/api/v1", api_v1_router())
         .route("/health", get(|| async { "OK" }))
         .nest_service(
             "/res",
@@ -186,47 +421,334 @@ async fn main() -> eyre::Result<()> {
                 .service(ServeDir::new("res")),
         )
         .with_state(state)
-        .layer(TraceLayer::new_for_http());
+        .layer(TraceLayer::new_for_http())
+}
 
-    let listener = tokio::net::TcpListener::bind("0.0.0.0:3000").await.unwrap();
-    axum::serve(listener, app.into_make_service())
-        .await
-        .unwrap();
-    Ok(())
+fn get_scheduled_rule<'a>(
+    schedule: &Schedule,
+    rules: &'a IndexMap<ArticleNr, Rule>,
+    date: Date,
+) -> Option<&'a Rule> {
+    match schedule.get_entry(date)?.resolve(rules)? {
+        RotationContent::Rule(rule) => Some(rule),
+        RotationContent::Interpretation(..) => None,
+    }
 }
 
-fn get_rule<'a>(
-    start_date: Date,
+fn is_day_available(state: &AppState, date: Date) -> bool {
+    date >= state.start_date
+        && (state.allow_future_days || date <= state.dynamic_state.read().unwrap().current_date)
+}
+
+fn get_rule_for_date(state: &AppState, date: Date) -> Option<&Rule> {
+    if !is_day_available(state, date) {
+        return None;
+    }
+    let dynamic_state = state.dynamic_state.read().unwrap();
+    get_scheduled_rule(
+        &dynamic_state.rotations[DEFAULT_ROTATION].schedule,
+        &state.rules,
+        date,
+    )
+}
+
+fn format_date(date: Date) -> String {
+    date.strftime("%d.%m.%Y").to_string()
+}
+
+fn get_random_rule<'a>(
+    rules: &'a IndexMap<ArticleNr, Rule>,
+    params: &RandomParams,
+) -> Option<&'a Rule> {
+    let candidates: Vec<_> = rules.values().filter(|r| params.matches(r)).collect();
+    if candidates.is_empty() {
+        return None;
+    }
+    let index = match &params.seed {
+        Some(seed) => {
+            let mut rng: Pcg64 = Seeder::from(seed.as_str()).into_rng();
+            rng.random_range(0..candidates.len())
+        }
+        None => rng().random_range(0..candidates.len()),
+    };
+    Some(candidates[index])
+}
+
+/// Published items of the last days for the feeds, newest first
+fn get_feed_history<'a>(
+    schedule: &Schedule,
     current_date: Date,
     rules: &'a IndexMap<ArticleNr, Rule>,
-    rule_order: &'a [usize],
-) -> &'a Rule {
-    let days_since_start = (current_date - start_date).get_days();
-    assert!(days_since_start >= 0);
-    &rules[rule_order[days_since_start as usize % rules.len()]]
+) -> Vec<(Date, RotationContent<'a>)> {
+    schedule
+        .entries()
+        .iter()
+        .rev()
+        .skip_while(|entry| entry.date > current_date)
+        .take(FEED_DAYS)
+        .filter_map(|entry| Some((entry.date, entry.resolve(rules)?)))
+        .collect()
+}
+
+fn get_feed_title(rotation: &RotationConfig) -> String {
+    if rotation.is_default() {
+        rotation.title.clone()
+    } else {
+        format!("Rule of the Day: {}", rotation.title)
+    }
 }
 
-fn build_rss(rule: &Rule) -> String {
-    let now =
-        rfc2822::to_string(&get_current_datetime()).expect("Could not format date as RFC 2822");
+fn get_feed_entry_id(rotation: &RotationConfig, date: Date) -> String {
+    format!("{}/day/{date}", rotation.to_url(PUB_URL))
+}
+
+fn render_feed_content(content: &RotationContent) -> String {
+    // Feed readers cannot resolve links relative to the site
+    html! { (content) }
+        .into_string()
+        .replace("href=\"/", &format!("href=\"{PUB_URL}/"))
+}
+
+fn to_rfc3339(date: Date) -> String {
+    get_start_of_day(date)
+        .strftime("%Y-%m-%dT%H:%M:%S%:z")
+        .to_string()
+}
+
+fn build_rss(
+    rotation: &RotationConfig,
+    current_date: Date,
+    history: &[(Date, RotationContent)],
+) -> String {
+    let to_rfc2822 = |date: Date| {
+        rfc2822::to_string(&get_start_of_day(date)).expect("Could not format date as RFC 2822")
+    };
+    let items = history
+        .iter()
+        .map(|(date, content)| {
+            ItemBuilder::default()
+                .pub_date(to_rfc2822(*date))
+                .title(content.to_title())
+                .link(content.to_url(PUB_URL))
+                .guid(
+                    GuidBuilder::default()
+                        .value(get_feed_entry_id(rotation, *date))
+                        .permalink(true)
+                        .build(),
+                )
+                .description(render_feed_content(content))
+                .build()
+        })
+        .collect::<Vec<_>>();
     ChannelBuilder::default()
-        .title("Rule of the Day")
-        .link(PUB_URL)
-        .description("Deine tägliche Dosis Regelwissen für American Football in Deutschland")
+        .title(get_feed_title(rotation))
+        .link(rotation.to_url(PUB_URL))
+        .description(DESCRIPTION)
         .language("de".to_string())
-        .last_build_date(now.clone())
-        .items(vec![
-            ItemBuilder::default()
-                .pub_date(now)
-                .title(rule.to_title())
-                .link(rule.to_url(PUB_URL))
-                .description(rule.to_description())
+        .last_build_date(to_rfc2822(current_date))
+        .items(items)
+        .build()
+        .to_string()
+}
+
+fn build_atom(
+    rotation: &RotationConfig,
+    current_date: Date,
+    history: &[(Date, RotationContent)],
+) -> String {
+    let to_datetime = |date: Date| -> FixedDateTime {
+        to_rfc3339(date)
+            .parse()
+            .expect("Could not parse RFC 3339 date")
+    };
+    let entries = history
+        .iter()
+        .map(|(date, content)| {
+            EntryBuilder::default()
+                .id(get_feed_entry_id(rotation, *date))
+                .title(content.to_title())
+                .updated(to_datetime(*date))
+                .published(Some(to_datetime(*date)))
+                .link(LinkBuilder::default().href(content.to_url(PUB_URL)).build())
+                .content(Some(
+                    ContentBuilder::default()
+                        .value(Some(render_feed_content(content)))
+                        .content_type(Some("html".to_string()))
+                        .build(),
+                ))
+                .build()
+        })
+        .collect::<Vec<_>>();
+    let rotation_url = rotation.to_url(PUB_URL);
+    FeedBuilder::default()
+        .id(rotation_url.clone())
+        .title(get_feed_title(rotation))
+        .subtitle(Some(DESCRIPTION.into()))
+        .lang(Some("de".to_string()))
+        .updated(to_datetime(current_date))
+        .link(LinkBuilder::default().href(rotation_url.clone()).build())
+        .link(
+            LinkBuilder::default()
+                .href(format!("{rotation_url}/atom.xml"))
+                .rel("self")
                 .build(),
-        ])
+        )
+        .entries(entries)
         .build()
         .to_string()
 }
 
+#[derive(Debug, Serialize)]
+struct JsonFeed {
+    version: &'static str,
+    title: String,
+    home_page_url: String,
+    feed_url: String,
+    description: &'static str,
+    language: &'static str,
+    items: Vec<JsonFeedItem>,
+}
+
+#[derive(Debug, Serialize)]
+struct JsonFeedItem {
+    id: String,
+    url: String,
+    title: String,
+    content_html: String,
+    date_published: String,
+}
+
+fn build_json_feed(rotation: &RotationConfig, history: &[(Date, RotationContent)]) -> String {
+    let rotation_url = rotation.to_url(PUB_URL);
+    let feed = JsonFeed {
+        version: "https://jsonfeed.org/version/1.1",
+        title: get_feed_title(rotation),
+        feed_url: format!("{rotation_url}/feed.json"),
+        home_page_url: rotation_url,
+        description: DESCRIPTION,
+        language: "de",
+        items: history
+            .iter()
+            .map(|(date, content)| JsonFeedItem {
+                id: get_feed_entry_id(rotation, *date),
+                url: content.to_url(PUB_URL),
+                title: content.to_title(),
+                content_html: render_feed_content(content),
+                date_published: to_rfc3339(*date),
+            })
+            .collect(),
+    };
+    serde_json::to_string(&feed).expect("Could not serialize JSON feed")
+}
+
+/// Escapes a text value of an iCalendar property
+fn escape_ics_text(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+}
+
+/// Folds a content line of an iCalendar file after at most 75 octets
+fn fold_ics_line(line: &str) -> String {
+    let mut folded = String::new();
+    let mut line_length = 0;
+    for c in line.chars() {
+        if line_length + c.len_utf8() > 75 {
+            folded.push_str("\r\n ");
+            line_length = 1;
+        }
+        folded.push(c);
+        line_length += c.len_utf8();
+    }
+    folded.push_str("\r\n");
+    folded
+}
+
+fn build_calendar(days: &[(Date, &Rule)], timestamp: Timestamp) -> String {
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        "PRODID:-//ruleoftheday.de//Rule of the Day//DE".to_string(),
+        "CALSCALE:GREGORIAN".to_string(),
+        "X-WR-CALNAME:Rule of the Day".to_string(),
+        format!("X-WR-CALDESC:{}", escape_ics_text(DESCRIPTION)),
+    ];
+    for (date, rule) in days {
+        let url = rule.to_url(PUB_URL);
+        lines.extend([
+            "BEGIN:VEVENT".to_string(),
+            format!("UID:{date}@ruleoftheday.de"),
+            format!("DTSTAMP:{}", timestamp.strftime("%Y%m%dT%H%M%SZ")),
+            format!("DTSTART;VALUE=DATE:{}", date.strftime("%Y%m%d")),
+            format!(
+                "DTEND;VALUE=DATE:{}",
+                date.tomorrow()
+                    .expect("Date is not the last day")
+                    .strftime("%Y%m%d")
+            ),
+            format!("SUMMARY:{}", escape_ics_text(&rule.to_title())),
+            format!(
+                "DESCRIPTION:{}",
+                escape_ics_text(&format!("{}\n{url}", rule.to_description()))
+            ),
+            format!("URL:{url}"),
+            "TRANSP:TRANSPARENT".to_string(),
+            "END:VEVENT".to_string(),
+        ]);
+    }
+    lines.push("END:VCALENDAR".to_string());
+    lines.iter().map(|line| fold_ics_line(line)).collect()
+}
+
+fn update_rotation_state(
+    rotation: &RotationConfig,
+    rotation_state: &mut RotationState,
+    rules: &IndexMap<ArticleNr, Rule>,
+    current_date: Date,
+) -> eyre::Result<()> {
+    rotation_state.schedule.publish_until(current_date, rules)?;
+    let entry = rotation_state.schedule.get_entry(current_date);
+    match entry.as_ref().and_then(|e| e.resolve(rules)) {
+        Some(content) => {
+            info!(
+                "Current item of rotation {}: {}",
+                rotation.name,
+                content.to_title()
+            );
+            rotation_state.discord_message = Some(build_discord_message(&content));
+            rotation_state.current_markup = html! { (content) };
+        }
+        None => {
+            // Happens after excluding rules or switching to a new edition of the rule book
+            warn!(
+                "Current item of rotation {} is not available anymore: {:?}",
+                rotation.name, entry
+            );
+            rotation_state.discord_message = None;
+            rotation_state.current_markup = render_unavailable_entry(entry.as_ref());
+        }
+    }
+    let history = get_feed_history(&rotation_state.schedule, current_date, rules);
+    rotation_state.rss = build_rss(rotation, current_date, &history);
+    rotation_state.atom = build_atom(rotation, current_date, &history);
+    rotation_state.json_feed = build_json_feed(rotation, &history);
+    Ok(())
+}
+
+fn render_unavailable_entry(entry: Option<&ScheduleEntry>) -> Markup {
+    html! {
+        article.message ."is-size-4" .is-warning {
+            div.message-header {
+                p { (entry.and_then(|e| e.title.as_deref()).unwrap_or("Nicht verfügbar")) }
+            }
+            div.message-body {
+                p { "Dieser Eintrag ist im aktuellen Regelwerk nicht mehr verfügbar." }
+            }
+        }
+    }
+}
+
 fn css() -> Markup {
     html! {
         link rel="stylesheet" type="text/css"
@@ -242,9 +764,12 @@ fn insert_content_to_site(content: &dyn Render) -> Markup {
                 (css())
                 meta name="viewport" content="width=device-width, initial-scale=1";
                 title { "Rule of the Day" }
-                meta name="description" property="description" content="Deine tägliche Dosis Regelwissen für American Football in Deutschland";
+                meta name="description" property="description" content=(DESCRIPTION);
                 meta property="og:title" content="Rule of the Day";
-                meta property="og:description" content="Deine tägliche Dosis Regelwissen für American Football in Deutschland";
+                meta property="og:description" content=(DESCRIPTION);
+                link rel="alternate" type="application/rss+xml" title="Rule of the Day (RSS)" href="/rss.xml";
+                link rel="alternate" type="application/atom+xml" title="Rule of the Day (Atom)" href="/atom.xml";
+                link rel="alternate" type="application/feed+json" title="Rule of the Day (JSON Feed)" href="/feed.json";
                 meta property="og:url" content=(PUB_URL);
                 meta property="og:image" content=(OPENGRAPH_PNG);
                 meta property="og:locale" content="de_DE";
@@ -310,56 +835,1738 @@ fn insert_content_to_site(content: &dyn Render) -> Markup {
     }
 }
 
-async fn get_random_rule(State(state): State<Arc<AppState>>) -> Markup {
-    let mut rng = rng();
-    let rules = &state.rules;
-    let rule_index = rng.random_range(0..rules.len() - 1);
-    let rule = &rules[rule_index];
+/// Whether the client prefers JSON over HTML according to its Accept header
+///
+/// Media types with a higher quality win, on equal quality the one that is listed first.
+fn accepts_json(headers: &HeaderMap) -> bool {
+    let media_ranges: Vec<(String, f32)> = headers
+        .get_all(header::ACCEPT)
+        .iter()
+        .filter_map(|accept| accept.to_str().ok())
+        .flat_map(|accept| accept.split(','))
+        .filter_map(|media_range| {
+            let mut parameters = media_range.split(';');
+            let media_type = parameters.next()?.trim().to_ascii_lowercase();
+            let quality = parameters
+                .filter_map(|p| p.trim().strip_prefix("q="))
+                .find_map(|q| q.trim().parse().ok())
+                .unwrap_or(1.0);
+            Some((media_type, quality))
+        })
+        .collect();
+    let preference = |media_type: &str| {
+        media_ranges
+            .iter()
+            .position(|(m, _)| m == media_type)
+            .map(|position| (media_ranges[position].1, position))
+    };
+    match (preference("application/json"), preference("text/html")) {
+        (Some((json, json_position)), Some((html, html_position))) => {
+            json > 0.0 && (json > html || (json == html && json_position < html_position))
+        }
+        (Some((json, _)), None) => json > 0.0,
+        _ => false,
+    }
+}
+
+async fn redirect_random_rule(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<RandomParams>,
+) -> Result<Redirect, StatusCode> {
+    let rule = get_random_rule(&state.rules, &params).ok_or(StatusCode::NOT_FOUND)?;
+    Ok(Redirect::to(&rule.to_url("")))
+}
+
+async fn get_current_rule(State(state): State<Arc<AppState>>, headers: HeaderMap) -> Response {
+    let dynamic_state = state.dynamic_state.read().unwrap();
+    let rotation_state = &dynamic_state.rotations[DEFAULT_ROTATION];
+    if accepts_json(&headers) {
+        let date = dynamic_state.current_date;
+        return match get_scheduled_rule(&rotation_state.schedule, &state.rules, date) {
+            Some(rule) => Json(DailyRule { date, rule }).into_response(),
+            None => StatusCode::NOT_FOUND.into_response(),
+        };
+    }
     insert_content_to_site(&html! {
         .container {
+            .block { (rotation_state.current_markup) }
+        }
+    })
+    .into_response()
+}
+
+async fn get_day_rule(
+    State(state): State<Arc<AppState>>,
+    Path(date): Path<String>,
+    headers: HeaderMap,
+) -> Result<Response, StatusCode> {
+    let date: Date = date.parse().map_err(|_| StatusCode::BAD_REQUEST)?;
+    let rule = get_rule_for_date(&state, date).ok_or(StatusCode::NOT_FOUND)?;
+    if accepts_json(&headers) {
+        return Ok(Json(DailyRule { date, rule }).into_response());
+    }
+    let previous_day = date
+        .yesterday()
+        .ok()
+        .filter(|d| is_day_available(&state, *d));
+    let next_day = date
+        .tomorrow()
+        .ok()
+        .filter(|d| is_day_available(&state, *d));
+    Ok(insert_content_to_site(&html! {
+        .container {
+            .block {
+                h2.title ."is-4" { "Regel des Tages vom " (format_date(date)) }
+            }
             .block { (rule) }
+            .block {
+                .buttons {
+                    @if let Some(previous_day) = previous_day {
+                        a .button .is-medium href=(format!("/day/{previous_day}")) { "Vorheriger Tag" }
+                    }
+                    a .button .is-medium href="/archive" { "Archiv" }
+                    @if let Some(next_day) = next_day {
+                        a .button .is-medium href=(format!("/day/{next_day}")) { "Nächster Tag" }
+                    }
+                }
+            }
         }
     })
+    .into_response())
 }
 
-async fn get_current_rule(State(state): State<Arc<AppState>>) -> Markup {
-    let rule = &state.dynamic_state.read().unwrap().current_rule_markup;
+async fn get_archive(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<ArchiveParams>,
+) -> Markup {
+    let days = params.days.unwrap_or(ARCHIVE_DAYS).min(MAX_ARCHIVE_DAYS);
+    let dynamic_state = state.dynamic_state.read().unwrap();
+    let schedule = &dynamic_state.rotations[DEFAULT_ROTATION].schedule;
+    let days: Vec<_> = dynamic_state
+        .current_date
+        .series(Span::new().days(-1))
+        .take(days.into())
+        .take_while(|date| *date >= state.start_date)
+        .filter_map(|date| Some((date, get_scheduled_rule(schedule, &state.rules, date)?)))
+        .collect();
     insert_content_to_site(&html! {
         .container {
-            .block { (rule) }
+            .block {
+                article.message ."is-size-4" {
+                    div.message-header {
+                        p { "Archiv" }
+                    }
+                    div.message-body {
+                        table.table .is-fullwidth .is-striped {
+                            thead {
+                                tr {
+                                    th { "Datum" }
+                                    th { "Regel" }
+                                }
+                            }
+                            tbody {
+                                @for (date, rule) in days {
+                                    tr {
+                                        td { a href=(format!("/day/{date}")) { (format_date(date)) } }
+                                        td { a href=(rule.to_url("")) { (rule.to_title()) } }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
         }
     })
 }
 
 async fn get_single_rule(
     State(state): State<Arc<AppState>>,
-    Path(article_nr): Path<String>,
-) -> Result<Markup, StatusCode> {
-    let article_nr =
-        ArticleNr::from_path_paramter(article_nr).map_err(|_| StatusCode::BAD_REQUEST)?;
+    Path(rule_ref): Path<String>,
+    headers: HeaderMap,
+) -> Result<Response, StatusCode> {
+    let rule_ref = RuleRef::from_path_parameter(&rule_ref).map_err(|_| StatusCode::BAD_REQUEST)?;
     let rules = &state.rules;
-    let rule = rules.get(&article_nr).ok_or(StatusCode::NOT_FOUND)?;
+    let rule = rules
+        .get(&rule_ref.article_nr)
+        .ok_or(StatusCode::NOT_FOUND)?;
+    if !rule_ref.item_path.is_empty() {
+        // Items are addressed by the fragment of the article page
+        rule_ref.resolve(rule).ok_or(StatusCode::NOT_FOUND)?;
+        return Ok(Redirect::permanent(&rule_ref.to_url("")).into_response());
+    }
+    if accepts_json(&headers) {
+        return Ok(Json(rule).into_response());
+    }
+    let index = rules.get_index_of(&rule.article_nr).unwrap();
+    let previous = index.checked_sub(1).and_then(|i| rules.get_index(i));
+    let next = rules.get_index(index + 1);
+    let section = find_section(&state.chapters, rule.article_nr.section_nr());
     Ok(insert_content_to_site(&html! {
         .container {
             .block { (rule) }
+            .block .buttons {
+                @if let Some((_, previous)) = previous {
+                    a .button .is-medium href=(previous.to_url("")) { "← " (previous.to_title()) }
+                }
+                @if let Some((_, section)) = section {
+                    a .button .is-medium href=(section.to_url("")) { (section.to_title()) }
+                }
+                @if let Some((_, next)) = next {
+                    a .button .is-medium href=(next.to_url("")) { (next.to_title()) " →" }
+                }
+            }
+        }
+    })
+    .into_response())
+}
+
+async fn get_chapter(
+    State(state): State<Arc<AppState>>,
+    Path(chapter_nr): Path<String>,
+) -> Result<Markup, StatusCode> {
+    let chapter_nr: u8 = chapter_nr.parse().map_err(|_| StatusCode::BAD_REQUEST)?;
+    let chapter = state
+        .chapters
+        .iter()
+        .find(|c| c.chapter_nr == chapter_nr)
+        .ok_or(StatusCode::NOT_FOUND)?;
+    Ok(insert_content_to_site(&html! {
+        .container {
+            .block {
+                article.message ."is-size-4" {
+                    div.message-header {
+                        p { (chapter.to_title()) }
+                    }
+                    div.message-body {
+                        .content { (chapter.render_contents(&state.rules)) }
+                    }
+                }
+            }
+            .block {
+                a .button .is-medium href="/all" { "Alle Regeln" }
+            }
         }
     }))
 }
 
-async fn get_all_rules(State(state): State<Arc<AppState>>) -> Markup {
-    let rules = &state.rules;
-    insert_content_to_site(&html! {
+async fn get_section(
+    State(state): State<Arc<AppState>>,
+    Path(section_nr): Path<String>,
+) -> Result<Markup, StatusCode> {
+    let section_nr =
+        SectionNr::from_path_parameter(&section_nr).map_err(|_| StatusCode::BAD_REQUEST)?;
+    let (chapter, section) =
+        find_section(&state.chapters, section_nr).ok_or(StatusCode::NOT_FOUND)?;
+    Ok(insert_content_to_site(&html! {
         .container {
-            @for (_article, rule) in rules.iter() {
+            .block {
+                article.message ."is-size-4" {
+                    div.message-header {
+                        p { (section.to_title()) }
+                    }
+                    div.message-body {
+                        a .button .is-medium href=(chapter.to_url("")) { (chapter.to_title()) }
+                    }
+                }
+            }
+            @for rule in section.rules(&state.rules) {
                 .block { (rule) }
             }
         }
+    }))
+}
+
+fn render_single_interpretation(
+    rules: &IndexMap<ArticleNr, Rule>,
+    interpretation: &RuleInterpretation,
+) -> Markup {
+    let rule = &rules[&interpretation.article_nr];
+    insert_content_to_site(&html! {
+        .container {
+            .block { (interpretation) }
+            .block {
+                a .button .is-medium href=(rule.to_url("")) { "Regel " (rule.to_title()) }
+            }
+        }
     })
 }
 
-async fn rss(State(state): State<Arc<AppState>>) -> (TypedHeader<ContentType>, String) {
-    (
-        TypedHeader("application/rss+xml".parse().unwrap()),
-        state.dynamic_state.read().unwrap().rss.clone(),
-    )
+async fn get_all_interpretations(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+) -> Response {
+    if accepts_json(&headers) {
+        return Json(
+            state
+                .rules
+                .values()
+                .flat_map(|r| &r.interpretations)
+                .collect::<Vec<_>>(),
+        )
+        .into_response();
+    }
+    insert_content_to_site(&html! {
+        .container {
+            .block {
+                article.message ."is-size-4" .is-info {
+                    div.message-header {
+                        p { "Anerkannte Regelungen" }
+                        p { a href="/quiz" { "Quiz" } }
+                    }
+                    div.message-body {
+                        .content {
+                            @for rule in state.rules.values().filter(|r| !r.interpretations.is_empty()) {
+                                h4 { a href=(rule.to_url("")) { (rule.to_title()) } }
+                                ul {
+                                    @for interpretation in &rule.interpretations {
+                                        li {
+                                            a href=(interpretation.to_ref().to_url("")) { (interpretation.get_title()) }
+                                            " " (interpretation.to_description())
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    })
+    .into_response()
+}
+
+async fn get_current_interpretation(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+) -> Result<Response, StatusCode> {
+    let dynamic_state = state.dynamic_state.read().unwrap();
+    let content = dynamic_state
+        .rotations
+        .get(INTERPRETATION_ROTATION)
+        .and_then(|r| r.schedule.get_entry(dynamic_state.current_date))
+        .and_then(|entry| entry.resolve(&state.rules));
+    let Some(RotationContent::Interpretation(_, interpretation)) = content else {
+        return Err(StatusCode::NOT_FOUND);
+    };
+    if accepts_json(&headers) {
+        return Ok(Json(interpretation).into_response());
+    }
+    Ok(render_single_interpretation(&state.rules, interpretation).into_response())
+}
+
+async fn get_random_interpretation(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+) -> Result<Response, StatusCode> {
+    let interpretation = *state
+        .rules
+        .values()
+        .flat_map(|r| &r.interpretations)
+        .collect::<Vec<_>>()
+        .choose(&mut rng())
+        .ok_or(StatusCode::NOT_FOUND)?;
+    if accepts_json(&headers) {
+        return Ok(Json(interpretation).into_response());
+    }
+    Ok(render_single_interpretation(&state.rules, interpretation).into_response())
+}
+
+async fn get_single_interpretation(
+    State(state): State<Arc<AppState>>,
+    Path((article_nr, index)): Path<(String, String)>,
+    headers: HeaderMap,
+) -> Result<Response, StatusCode> {
+    let interpretation_ref = InterpretationRef::from_path_parameters(&article_nr, &index)
+        .map_err(|_| StatusCode::BAD_REQUEST)?;
+    let interpretation = state
+        .rules
+        .get(&interpretation_ref.article_nr)
+        .and_then(|r| {
+            r.interpretations
+                .iter()
+                .find(|i| i.index == interpretation_ref.index)
+        })
+        .ok_or(StatusCode::NOT_FOUND)?;
+    if accepts_json(&headers) {
+        return Ok(Json(interpretation).into_response());
+    }
+    Ok(render_single_interpretation(&state.rules, interpretation).into_response())
+}
+
+fn find_interpretation<'a>(
+    rules: &'a IndexMap<ArticleNr, Rule>,
+    article_nr: &str,
+    index: &str,
+) -> Result<&'a RuleInterpretation, StatusCode> {
+    InterpretationRef::from_path_parameters(article_nr, index)
+        .map_err(|_| StatusCode::BAD_REQUEST)?
+        .resolve(rules)
+        .ok_or(StatusCode::NOT_FOUND)
+}
+
+fn get_quiz_score(headers: &HeaderMap) -> QuizScore {
+    let cookies: Vec<_> = headers
+        .get_all(header::COOKIE)
+        .iter()
+        .filter_map(|c| c.to_str().ok())
+        .collect();
+    QuizScore::from_cookies(&cookies.join("; "))
+}
+
+fn render_quiz_header(params: QuizParams, score: QuizScore) -> Markup {
+    let mode_url = |mode| format!("/quiz?{}", QuizParams { mode, ..params }.to_query());
+    html! {
+        h2.title ."is-4" { "Quiz" }
+        p.subtitle ."is-6" {
+            "Punktestand: " (score.correct) " von " (score.total) " richtig • "
+            a href=(mode_url(QuizMode::Choice)) { "Multiple Choice" } " • "
+            a href=(mode_url(QuizMode::SelfGraded)) { "Selbst bewerten" }
+        }
+        form method="post" action="/quiz/reset" {
+            button.button .is-small type="submit" { "Punktestand zurücksetzen" }
+        }
+    }
+}
+
+async fn redirect_random_quiz(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<QuizParams>,
+) -> Result<Redirect, StatusCode> {
+    let candidates: Vec<_> = state
+        .rules
+        .values()
+        .filter(|r| params.chapter.is_none_or(|c| c == r.article_nr.0))
+        .flat_map(|r| &r.interpretations)
+        .collect();
+    if candidates.is_empty() {
+        return Err(StatusCode::NOT_FOUND);
+    }
+    let interpretation = candidates[rng().random_range(0..candidates.len())];
+    let question = QuizQuestion::new(&state.rules, interpretation);
+    Ok(Redirect::to(&format!(
+        "{}?{}",
+        question.to_url(""),
+        params.to_query()
+    )))
+}
+
+async fn get_quiz_question(
+    State(state): State<Arc<AppState>>,
+    Path((article_nr, index)): Path<(String, String)>,
+    Query(params): Query<QuizParams>,
+    headers: HeaderMap,
+) -> Result<Markup, StatusCode> {
+    let interpretation = find_interpretation(&state.rules, &article_nr, &index)?;
+    let question = QuizQuestion::new(&state.rules, interpretation);
+    Ok(insert_content_to_site(&html! {
+        .container {
+            .block { (render_quiz_header(params, get_quiz_score(&headers))) }
+            .block {
+                article.message ."is-size-5" .is-info {
+                    div.message-header {
+                        p { "Situation" }
+                    }
+                    div.message-body {
+                        p { (render_with_links(&interpretation.text)) }
+                    }
+                }
+            }
+            .block {
+                form method="post" action=(question.to_url("")) {
+                    input type="hidden" name="mode" value=(params.mode.to_query_value());
+                    @if let Some(chapter) = params.chapter {
+                        input type="hidden" name="chapter" value=(chapter);
+                    }
+                    @match params.mode {
+                        QuizMode::Choice => {
+                            @for (i, option) in question.options.iter().enumerate() {
+                                .field {
+                                    label.radio {
+                                        input type="radio" name="choice" value=(i) required;
+                                        " " (option)
+                                    }
+                                }
+                            }
+                            button.button .is-medium .is-info type="submit" { "Antworten" }
+                        }
+                        QuizMode::SelfGraded => {
+                            details {
+                                summary { b { "Regelung" } }
+                                p { (render_with_links(&interpretation.ruling)) }
+                            }
+                            .buttons {
+                                button.button .is-medium .is-success type="submit" name="correct" value="true" { "Gewusst" }
+                                button.button .is-medium .is-danger type="submit" name="correct" value="false" { "Nicht gewusst" }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }))
+}
+
+async fn answer_quiz_question(
+    State(state): State<Arc<AppState>>,
+    Path((article_nr, index)): Path<(String, String)>,
+    headers: HeaderMap,
+    Form(answer): Form<QuizAnswer>,
+) -> Result<Response, StatusCode> {
+    let interpretation = find_interpretation(&state.rules, &article_nr, &index)?;
+    let question = QuizQuestion::new(&state.rules, interpretation);
+    let correct = match answer.mode {
+        QuizMode::Choice => question.is_correct(answer.choice.ok_or(StatusCode::BAD_REQUEST)?),
+        QuizMode::SelfGraded => answer.correct.ok_or(StatusCode::BAD_REQUEST)?,
+    };
+    let wrong_choice = answer
+        .choice
+        .filter(|_| !correct)
+        .and_then(|c| question.options.get(c));
+
+    let mut score = get_quiz_score(&headers);
+    score.add_answer(correct);
+    let params = QuizParams {
+        chapter: answer.chapter,
+        mode: answer.mode,
+    };
+    let rule = &state.rules[&interpretation.article_nr];
+    let markup = insert_content_to_site(&html! {
+        .container {
+            .block { (render_quiz_header(params, score)) }
+            .block {
+                article.message ."is-size-5" .is-success[correct] .is-danger[!correct] {
+                    div.message-header {
+                        p { @if correct { "Richtig!" } @else { "Leider falsch." } }
+                        p { a href=(interpretation.to_ref().to_url("")) { (interpretation.get_title()) } }
+                    }
+                    div.message-body {
+                        p { (render_with_links(&interpretation.text)) }
+                        @if let Some(wrong_choice) = wrong_choice {
+                            p { b { "Deine Antwort: " } (wrong_choice) }
+                        }
+                        p { b { "Regelung: " } (render_with_links(&interpretation.ruling)) }
+                    }
+                }
+            }
+            .block .buttons {
+                a .button .is-medium .is-info href=(format!("/quiz?{}", params.to_query())) { "Nächste Frage" }
+                a .button .is-medium href=(rule.to_url("")) { "Regel " (rule.to_title()) }
+            }
+        }
+    });
+    Ok(([(header::SET_COOKIE, score.to_cookie())], markup).into_response())
+}
+
+async fn reset_quiz_score() -> impl IntoResponse {
+    (
+        [(header::SET_COOKIE, QuizScore::reset_cookie())],
+        Redirect::to("/quiz"),
+    )
+}
+
+async fn get_signal(
+    State(state): State<Arc<AppState>>,
+    Path(signal_nr): Path<String>,
+) -> Result<Markup, StatusCode> {
+    let signal_nr: u8 = signal_nr
+        .trim_start_matches('S')
+        .parse()
+        .map_err(|_| StatusCode::BAD_REQUEST)?;
+    let signal = state.signals.get(&signal_nr).ok_or(StatusCode::NOT_FOUND)?;
+    let rules: Vec<_> = state
+        .rules
+        .values()
+        .filter(|r| r.uses_signal(signal_nr))
+        .collect();
+    Ok(insert_content_to_site(&html! {
+        .container {
+            .block {
+                article.message ."is-size-4" {
+                    div.message-header {
+                        p { (signal.to_title()) }
+                    }
+                    div.message-body {
+                        .content {
+                            @if rules.is_empty() {
+                                p { "Dieses Signal wird in keiner Regel verwendet." }
+                            } @else {
+                                p { "Regeln mit diesem Signal:" }
+                                ul {
+                                    @for rule in rules {
+                                        li { a href=(rule.to_url("")) { (rule.to_title()) } }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }))
+}
+
+async fn get_changes(State(state): State<Arc<AppState>>) -> Markup {
+    let rule_changes = |kind| {
+        html! {
+            ul {
+                @for change in state.changes.rules.iter().filter(|c| c.kind == kind) {
+                    li {
+                        a href=(change.rule_ref.to_url("")) { (change.reference()) }
+                        " " (change.description)
+                    }
+                }
+            }
+        }
+    };
+    let interpretation_changes = |kind| {
+        html! {
+            ul {
+                @for change in state.changes.interpretations.iter().filter(|c| c.kind == kind) {
+                    li {
+                        a href=(change.to_ref().to_url("")) { (change.get_title()) }
+                    }
+                }
+            }
+        }
+    };
+    insert_content_to_site(&html! {
+        .container {
+            .block {
+                article.message ."is-size-4" .is-warning {
+                    div.message-header {
+                        p { "Änderungen für die Saison" }
+                    }
+                    div.message-body {
+                        .content {
+                            h4 { "Regeländerungen" }
+                            (rule_changes(RuleChangeKind::Rule))
+                            h4 { "Editorische Änderungen" }
+                            (rule_changes(RuleChangeKind::Editorial))
+                            h4 { "Neue Interpretationen" }
+                            (interpretation_changes(InterpretationChangeKind::New))
+                            h4 { "Geänderte Interpretationen" }
+                            (interpretation_changes(InterpretationChangeKind::Changed))
+                        }
+                    }
+                }
+            }
+        }
+    })
+}
+
+async fn get_all_standards(State(state): State<Arc<AppState>>) -> Markup {
+    insert_content_to_site(&html! {
+        .container {
+            @for section in &state.standards {
+                .block { (section) }
+            }
+        }
+    })
+}
+
+async fn get_standard_section(
+    State(state): State<Arc<AppState>>,
+    Path(section_nr): Path<String>,
+) -> Result<Markup, StatusCode> {
+    let section_nr =
+        SectionNr::from_path_parameter(&section_nr).map_err(|_| StatusCode::BAD_REQUEST)?;
+    let section = state
+        .standards
+        .iter()
+        .find(|s| s.section_nr == section_nr)
+        .ok_or(StatusCode::NOT_FOUND)?;
+    Ok(insert_content_to_site(&html! {
+        .container {
+            .block { (section) }
+        }
+    }))
+}
+
+async fn get_single_standard(
+    State(state): State<Arc<AppState>>,
+    Path((section_nr, index)): Path<(String, String)>,
+) -> Result<Markup, StatusCode> {
+    let section_nr =
+        SectionNr::from_path_parameter(&section_nr).map_err(|_| StatusCode::BAD_REQUEST)?;
+    let index: usize = index.parse().map_err(|_| StatusCode::BAD_REQUEST)?;
+    let standard = state
+        .standards
+        .iter()
+        .find(|s| s.section_nr == section_nr)
+        .and_then(|s| s.standards.get(index.checked_sub(1)?))
+        .ok_or(StatusCode::NOT_FOUND)?;
+    Ok(insert_content_to_site(&html! {
+        .container {
+            .block { (standard) }
+        }
+    }))
+}
+
+async fn get_search(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<SearchParams>,
+    headers: HeaderMap,
+) -> Response {
+    let query = params.q.unwrap_or_default();
+    let results = state.search_index.search(&query, SEARCH_LIMIT);
+    if accepts_json(&headers) {
+        return Json(results).into_response();
+    }
+    insert_content_to_site(&html! {
+        .container {
+            .block {
+                form action="/search" method="get" {
+                    .field .has-addons {
+                        .control .is-expanded {
+                            input.input .is-medium type="search" name="q" value=(query)
+                                placeholder="z.B. Fair Catch-Signal";
+                        }
+                        .control {
+                            button.button .is-info .is-medium type="submit" { "Suchen" }
+                        }
+                    }
+                }
+            }
+            @if !query.trim().is_empty() {
+                .block {
+                    @if results.is_empty() {
+                        p { "Keine Treffer gefunden." }
+                    } @else {
+                        p { (results.len()) " Treffer" }
+                    }
+                }
+                @for result in &results {
+                    .block { (result) }
+                }
+            }
+        }
+    })
+    .into_response()
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/search",
+    params(SearchParams),
+    responses((status = 200, description = "Matching rules and interpretations ordered by relevance", body = [SearchResult]))
+)]
+async fn get_search_json(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<SearchParams>,
+) -> Json<Vec<SearchResult>> {
+    Json(
+        state
+            .search_index
+            .search(&params.q.unwrap_or_default(), SEARCH_LIMIT),
+    )
+}
+
+async fn get_all_rules(State(state): State<Arc<AppState>>, headers: HeaderMap) -> Response {
+    let rules = &state.rules;
+    if accepts_json(&headers) {
+        return Json(rules.values().collect::<Vec<_>>()).into_response();
+    }
+    insert_content_to_site(&html! {
+        .container {
+            .block { (render_table_of_contents(&state.chapters, rules)) }
+            @for (_article, rule) in rules.iter() {
+                .block { (rule) }
+            }
+        }
+    })
+    .into_response()
+}
+
+#[derive(OpenApi)]
+#[openapi(
+    info(title = "Rule of the Day"),
+    paths(
+        api_get_rules,
+        api_get_random_rule,
+        api_get_rule,
+        api_get_rule_interpretations,
+        api_get_interpretations,
+        api_get_interpretation,
+        api_get_today,
+        api_get_day,
+        get_search_json
+    )
+)]
+struct ApiDoc;
+
+fn api_v1_router() -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/rules", get(api_get_rules))
+        .route("/rules/random", get(api_get_random_rule))
+        .route("/rules/{article_nr}", get(api_get_rule))
+        .route(
+            "/rules/{article_nr}/interpretations",
+            get(api_get_rule_interpretations),
+        )
+        .route("/interpretations", get(api_get_interpretations))
+        .route(
+            "/interpretations/{article_nr}/{index}",
+            get(api_get_interpretation),
+        )
+        .route("/today", get(api_get_today))
+        .route("/day/{date}", get(api_get_day))
+        .route("/search", get(get_search_json))
+}
+
+fn find_rule(state: &AppState, article_nr: String) -> Result<&Rule, StatusCode> {
+    let article_nr =
+        ArticleNr::from_path_paramter(article_nr).map_err(|_| StatusCode::BAD_REQUEST)?;
+    state.rules.get(&article_nr).ok_or(StatusCode::NOT_FOUND)
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/rules",
+    responses((status = 200, description = "All rules", body = [Rule]))
+)]
+async fn api_get_rules(State(state): State<Arc<AppState>>) -> Response {
+    Json(state.rules.values().collect::<Vec<_>>()).into_response()
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/rules/{article_nr}",
+    params(("article_nr" = String, Path, description = "Article number like 7-1-5")),
+    responses(
+        (status = 200, description = "Rule with the article number", body = Rule),
+        (status = 400, description = "Invalid article number"),
+        (status = 404, description = "Rule not found")
+    )
+)]
+async fn api_get_rule(
+    State(state): State<Arc<AppState>>,
+    Path(article_nr): Path<String>,
+) -> Result<Response, StatusCode> {
+    Ok(Json(find_rule(&state, article_nr)?).into_response())
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/rules/{article_nr}/interpretations",
+    params(("article_nr" = String, Path, description = "Article number like 7-1-5")),
+    responses(
+        (status = 200, description = "Interpretations of the rule", body = [RuleInterpretation]),
+        (status = 400, description = "Invalid article number"),
+        (status = 404, description = "Rule not found")
+    )
+)]
+async fn api_get_rule_interpretations(
+    State(state): State<Arc<AppState>>,
+    Path(article_nr): Path<String>,
+) -> Result<Response, StatusCode> {
+    Ok(Json(&find_rule(&state, article_nr)?.interpretations).into_response())
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/rules/random",
+    params(RandomParams),
+    responses(
+        (status = 200, description = "Random rule", body = Rule),
+        (status = 404, description = "No rule matches the filters")
+    )
+)]
+async fn api_get_random_rule(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<RandomParams>,
+) -> Result<Response, StatusCode> {
+    let rule = get_random_rule(&state.rules, &params).ok_or(StatusCode::NOT_FOUND)?;
+    Ok(Json(rule).into_response())
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/interpretations",
+    responses((status = 200, description = "All interpretations", body = [RuleInterpretation]))
+)]
+async fn api_get_interpretations(State(state): State<Arc<AppState>>) -> Response {
+    Json(
+        state
+            .rules
+            .values()
+            .flat_map(|r| &r.interpretations)
+            .collect::<Vec<_>>(),
+    )
+    .into_response()
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/interpretations/{article_nr}/{index}",
+    params(
+        ("article_nr" = String, Path, description = "Article number like 7-1-5"),
+        ("index" = String, Path, description = "Roman number of the interpretation like IV")
+    ),
+    responses(
+        (status = 200, description = "Interpretation", body = RuleInterpretation),
+        (status = 400, description = "Invalid article number or index"),
+        (status = 404, description = "Interpretation not found")
+    )
+)]
+async fn api_get_interpretation(
+    State(state): State<Arc<AppState>>,
+    Path((article_nr, index)): Path<(String, String)>,
+) -> Result<Response, StatusCode> {
+    Ok(Json(find_interpretation(&state.rules, &article_nr, &index)?).into_response())
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/today",
+    responses((status = 200, description = "Rule of the current day", body = DailyRule))
+)]
+async fn api_get_today(State(state): State<Arc<AppState>>) -> Response {
+    let date = state.dynamic_state.read().unwrap().current_date;
+    api_get_day(State(state), Path(date)).await
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/day/{date}",
+    params(("date" = String, Path, format = Date, description = "Date like 2026-04-01")),
+    responses(
+        (status = 200, description = "Rule of the day", body = DailyRule),
+        (status = 400, description = "Invalid date"),
+        (status = 404, description = "Date is before the first rule of the day or in the future")
+    )
+)]
+async fn api_get_day(State(state): State<Arc<AppState>>, Path(date): Path<Date>) -> Response {
+    match get_rule_for_date(&state, date) {
+        Some(rule) => Json(DailyRule { date, rule }).into_response(),
+        None => StatusCode::NOT_FOUND.into_response(),
+    }
+}
+
+fn get_rotation_feed(
+    state: &AppState,
+    name: &str,
+    content_type: &str,
+    feed: fn(&RotationState) -> &String,
+) -> Result<(TypedHeader<ContentType>, String), StatusCode> {
+    let dynamic_state = state.dynamic_state.read().unwrap();
+    let rotation_state = dynamic_state
+        .rotations
+        .get(name)
+        .ok_or(StatusCode::NOT_FOUND)?;
+    Ok((
+        TypedHeader(content_type.parse().unwrap()),
+        feed(rotation_state).clone(),
+    ))
+}
+
+async fn rss(
+    State(state): State<Arc<AppState>>,
+) -> Result<(TypedHeader<ContentType>, String), StatusCode> {
+    rotation_rss(State(state), Path(DEFAULT_ROTATION.to_string())).await
+}
+
+async fn atom(
+    State(state): State<Arc<AppState>>,
+) -> Result<(TypedHeader<ContentType>, String), StatusCode> {
+    rotation_atom(State(state), Path(DEFAULT_ROTATION.to_string())).await
+}
+
+async fn json_feed(
+    State(state): State<Arc<AppState>>,
+) -> Result<(TypedHeader<ContentType>, String), StatusCode> {
+    rotation_json_feed(State(state), Path(DEFAULT_ROTATION.to_string())).await
+}
+
+async fn rotation_rss(
+    State(state): State<Arc<AppState>>,
+    Path(name): Path<String>,
+) -> Result<(TypedHeader<ContentType>, String), StatusCode> {
+    get_rotation_feed(&state, &name, "application/rss+xml", |r| &r.rss)
+}
+
+async fn rotation_atom(
+    State(state): State<Arc<AppState>>,
+    Path(name): Path<String>,
+) -> Result<(TypedHeader<ContentType>, String), StatusCode> {
+    get_rotation_feed(&state, &name, "application/atom+xml", |r| &r.atom)
+}
+
+async fn rotation_json_feed(
+    State(state): State<Arc<AppState>>,
+    Path(name): Path<String>,
+) -> Result<(TypedHeader<ContentType>, String), StatusCode> {
+    get_rotation_feed(&state, &name, "application/feed+json", |r| &r.json_feed)
+}
+
+fn describe_pace(rotation: &RotationConfig) -> String {
+    match rotation.interval_days {
+        1 => "Täglich".to_string(),
+        7 => "Wöchentlich".to_string(),
+        days => format!("Alle {days} Tage"),
+    }
+}
+
+async fn get_rotations(State(state): State<Arc<AppState>>) -> Markup {
+    insert_content_to_site(&html! {
+        .container {
+            .block {
+                article.message ."is-size-4" {
+                    div.message-header {
+                        p { "Rotationen" }
+                    }
+                    div.message-body {
+                        table.table .is-fullwidth .is-striped {
+                            thead {
+                                tr {
+                                    th { "Rotation" }
+                                    th { "Rhythmus" }
+                                    th { "Feed" }
+                                }
+                            }
+                            tbody {
+                                @for rotation in state.rotations.values() {
+                                    @let url = rotation.to_url("");
+                                    @let page_url = if rotation.is_default() { "/".to_string() } else { url.clone() };
+                                    tr {
+                                        td { a href=(page_url) { (rotation.title) } }
+                                        td { (describe_pace(rotation)) }
+                                        td { a href=(format!("{url}/rss.xml")) { "RSS" } }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    })
+}
+
+async fn get_rotation(
+    State(state): State<Arc<AppState>>,
+    Path(name): Path<String>,
+) -> Result<Response, StatusCode> {
+    let rotation = state.rotations.get(&name).ok_or(StatusCode::NOT_FOUND)?;
+    if rotation.is_default() {
+        return Ok(Redirect::permanent("/").into_response());
+    }
+    let dynamic_state = state.dynamic_state.read().unwrap();
+    let rotation_url = rotation.to_url("");
+    Ok(insert_content_to_site(&html! {
+        .container {
+            .block {
+                h2.title ."is-4" { (rotation.title) }
+                p.subtitle ."is-6" {
+                    (describe_pace(rotation)) " • "
+                    a href=(format!("{rotation_url}/rss.xml")) { "RSS" } " • "
+                    a href=(format!("{rotation_url}/atom.xml")) { "Atom" } " • "
+                    a href=(format!("{rotation_url}/feed.json")) { "JSON Feed" }
+                }
+            }
+            .block { (dynamic_state.rotations[&name].current_markup) }
+        }
+    })
+    .into_response())
+}
+
+async fn get_rotation_day(
+    State(state): State<Arc<AppState>>,
+    Path((name, date)): Path<(String, String)>,
+) -> Result<Response, StatusCode> {
+    let date: Date = date.parse().map_err(|_| StatusCode::BAD_REQUEST)?;
+    let rotation = state.rotations.get(&name).ok_or(StatusCode::NOT_FOUND)?;
+    if rotation.is_default() {
+        return Ok(Redirect::permanent(&format!("/day/{date}")).into_response());
+    }
+    let dynamic_state = state.dynamic_state.read().unwrap();
+    if !state.allow_future_days && date > dynamic_state.current_date {
+        return Err(StatusCode::NOT_FOUND);
+    }
+    let content = dynamic_state.rotations[&name]
+        .schedule
+        .get_entry(date)
+        .and_then(|entry| entry.resolve(&state.rules))
+        .ok_or(StatusCode::NOT_FOUND)?;
+    Ok(insert_content_to_site(&html! {
+        .container {
+            .block {
+                h2.title ."is-4" {
+                    a href=(rotation.to_url("")) { (rotation.title) } " vom " (format_date(date))
+                }
+            }
+            .block { (content) }
+        }
+    })
+    .into_response())
+}
+
+/// Recent and upcoming rules of the day for calendar apps
+///
+/// Future days are only included with `--allow-future-days`, otherwise the calendar ends today.
+async fn calendar(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<CalendarParams>,
+) -> (TypedHeader<ContentType>, String) {
+    let days_before = params
+        .days_before
+        .unwrap_or(CALENDAR_DAYS_BEFORE)
+        .min(MAX_ARCHIVE_DAYS);
+    let days_after = if state.allow_future_days {
+        params
+            .days_after
+            .unwrap_or(CALENDAR_DAYS_AFTER)
+            .min(MAX_ARCHIVE_DAYS)
+    } else {
+        0
+    };
+    let dynamic_state = state.dynamic_state.read().unwrap();
+    let current_date = dynamic_state.current_date;
+    let schedule = &dynamic_state.rotations[DEFAULT_ROTATION].schedule;
+    let days: Vec<_> = current_date
+        .checked_sub(Span::new().days(days_before))
+        .unwrap_or(current_date)
+        .series(Span::new().days(1))
+        .take(usize::from(days_before) + usize::from(days_after) + 1)
+        .filter(|date| *date >= state.start_date)
+        .filter_map(|date| Some((date, get_scheduled_rule(schedule, &state.rules, date)?)))
+        .collect();
+    (
+        TypedHeader("text/calendar; charset=utf-8".parse().unwrap()),
+        build_calendar(&days, Timestamp::now()),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use axum::{
+        body::{Body, to_bytes},
+        http::Request,
+    };
+    use insta::assert_yaml_snapshot;
+    use once_cell::sync::Lazy;
+    use serde_json::Value;
+    use std::collections::HashSet;
+    use tower::Service;
+
+    const RULES_TEXT_FIXTURE: &str = include_str!("../tests/fixtures/Football_Regelbuch_2026.txt");
+
+    /// Rules of the text fixture that are parsed only once, the binary cannot use the one of the library
+    fn parsed_rules_fixture() -> &'static IndexMap<ArticleNr, Rule> {
+        static RULES: Lazy<IndexMap<ArticleNr, Rule>> = Lazy::new(|| {
+            parser::RulesParser::default()
+                .parse_text(RULES_TEXT_FIXTURE.to_string())
+                .unwrap()
+        });
+        &RULES
+    }
+
+    fn collect_refs<'a>(value: &'a Value, refs: &mut Vec<&'a str>) {
+        match value {
+            Value::Object(object) => {
+                for (key, value) in object {
+                    match (key.as_str(), value) {
+                        ("$ref", Value::String(reference)) => refs.push(reference),
+                        _ => collect_refs(value, refs),
+                    }
+                }
+            }
+            Value::Array(values) => values.iter().for_each(|v| collect_refs(v, refs)),
+            _ => {}
+        }
+    }
+
+    fn default_rotation(start_date: Date) -> RotationConfig {
+        RotationConfig {
+            name: DEFAULT_ROTATION.to_string(),
+            title: "Rule of the Day".to_string(),
+            pool: RotationPool::Rules,
+            chapters: vec![],
+            start_date,
+            seed: None,
+            interval_days: 1,
+            weighting: None,
+            schedule_path: None,
+            discord_channel_id: None,
+            discord_post_hour: None,
+        }
+    }
+
+    fn test_state(start_date: Date, current_date: Date, allow_future_days: bool) -> Arc<AppState> {
+        let rules_parser = parser::RulesParser::default();
+        let signals = rules_parser.extract_signals(RULES_TEXT_FIXTURE).unwrap();
+        let standards = rules_parser.extract_standards(RULES_TEXT_FIXTURE).unwrap();
+        let chapters = rules_parser.extract_chapters(RULES_TEXT_FIXTURE).unwrap();
+        let changes = rules_parser.extract_changes(RULES_TEXT_FIXTURE).unwrap();
+        let rules = parsed_rules_fixture().clone();
+        let search_index = SearchIndex::new(&rules);
+
+        let rotations = IndexMap::from([
+            (DEFAULT_ROTATION.to_string(), default_rotation(start_date)),
+            (
+                INTERPRETATION_ROTATION.to_string(),
+                RotationConfig::interpretation_of_the_day(start_date),
+            ),
+        ]);
+        let rotation_states = rotations
+            .iter()
+            .map(|(name, rotation)| {
+                let mut rotation_state = RotationState {
+                    schedule: Schedule::load_for_rules(None, rotation, &rules).unwrap(),
+                    current_markup: html! {},
+                    rss: String::new(),
+                    atom: String::new(),
+                    json_feed: String::new(),
+                    discord_message: None,
+                };
+                update_rotation_state(rotation, &mut rotation_state, &rules, current_date).unwrap();
+                (name.clone(), rotation_state)
+            })
+            .collect();
+
+        Arc::new(AppState {
+            rules,
+            chapters,
+            signals,
+            standards,
+            changes,
+            search_index,
+            start_date,
+            allow_future_days,
+            rotations,
+            dynamic_state: RwLock::new(DynamicState {
+                current_date,
+                rotations: rotation_states,
+            }),
+        })
+    }
+
+    async fn send_request(router: &mut Router, uri: &str, accept: Option<&str>) -> Response {
+        let mut request = Request::builder().uri(uri);
+        if let Some(accept) = accept {
+            request = request.header(header::ACCEPT, accept);
+        }
+        router
+            .call(request.body(Body::empty()).unwrap())
+            .await
+            .unwrap()
+    }
+
+    async fn get_text(router: &mut Router, uri: &str) -> String {
+        let response = send_request(router, uri, None).await;
+        assert_eq!(response.status(), StatusCode::OK, "{uri}");
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        String::from_utf8(body.to_vec()).unwrap()
+    }
+
+    async fn get_json(router: &mut Router, uri: &str) -> Value {
+        let response = send_request(router, uri, None).await;
+        assert_eq!(response.status(), StatusCode::OK, "{uri}");
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        serde_json::from_slice(&body).unwrap()
+    }
+
+    #[test]
+    fn test_accepts_json() {
+        let accepts = |accept: &str| {
+            let mut headers = HeaderMap::new();
+            headers.insert(header::ACCEPT, HeaderValue::from_str(accept).unwrap());
+            accepts_json(&headers)
+        };
+        assert!(!accepts_json(&HeaderMap::new()));
+        assert!(accepts("application/json"));
+        assert!(accepts("Application/JSON"));
+        assert!(accepts("application/json, text/html"));
+        assert!(!accepts("text/html, application/json"));
+        assert!(accepts("text/html;q=0.1, application/json"));
+        assert!(accepts("text/html; q=0.5, application/json; q=0.9"));
+        assert!(!accepts("application/json;q=0.5, text/html"));
+        assert!(!accepts("application/json;q=0"));
+        assert!(!accepts(
+            "text/html,application/xhtml+xml,application/xml;q=0.9,*/*;q=0.8"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_api_v1() {
+        let state = test_state(
+            Date::constant(2026, 1, 1),
+            Date::constant(2026, 1, 20),
+            false,
+        );
+        let mut router = build_router(state.clone());
+
+        let rules = get_json(&mut router, "/api/v1/rules").await;
+        assert_eq!(rules.as_array().unwrap().len(), state.rules.len());
+
+        let rule = get_json(&mut router, "/api/v1/rules/9-1-10").await;
+        assert_eq!(
+            rule,
+            serde_json::to_value(&state.rules[&ArticleNr(9, 1, 10)]).unwrap()
+        );
+        for (uri, status) in [
+            ("/api/v1/rules/99-1-1", StatusCode::NOT_FOUND),
+            ("/api/v1/rules/nine", StatusCode::BAD_REQUEST),
+            ("/api/v1/rules/random?chapter=99", StatusCode::NOT_FOUND),
+            ("/api/v1/interpretations/9-1-10/XX", StatusCode::NOT_FOUND),
+            ("/api/v1/interpretations/9-1-10/1", StatusCode::BAD_REQUEST),
+            ("/api/v1/day/2025-12-31", StatusCode::NOT_FOUND),
+            ("/api/v1/day/2026-01-21", StatusCode::NOT_FOUND),
+            ("/api/v1/day/yesterday", StatusCode::BAD_REQUEST),
+        ] {
+            assert_eq!(
+                send_request(&mut router, uri, None).await.status(),
+                status,
+                "{uri}"
+            );
+        }
+
+        let interpretations = get_json(&mut router, "/api/v1/rules/9-1-10/interpretations").await;
+        assert_eq!(
+            interpretations.as_array().unwrap().len(),
+            state.rules[&ArticleNr(9, 1, 10)].interpretations.len()
+        );
+
+        let random = get_json(&mut router, "/api/v1/rules/random?chapter=9").await;
+        assert_eq!(random["article_nr"][0], 9);
+
+        let interpretations = get_json(&mut router, "/api/v1/interpretations").await;
+        assert_eq!(
+            interpretations.as_array().unwrap().len(),
+            state
+                .rules
+                .values()
+                .map(|r| r.interpretations.len())
+                .sum::<usize>()
+        );
+        let interpretation = get_json(&mut router, "/api/v1/interpretations/9-1-10/II").await;
+        assert_eq!(
+            interpretation,
+            serde_json::to_value(state.rules[&ArticleNr(9, 1, 10)].interpretations[1].clone())
+                .unwrap()
+        );
+
+        let today = get_json(&mut router, "/api/v1/today").await;
+        assert_eq!(today["date"], "2026-01-20");
+        assert_eq!(today, get_json(&mut router, "/api/v1/day/2026-01-20").await);
+        let first_day = get_json(&mut router, "/api/v1/day/2026-01-01").await;
+        assert_eq!(first_day["date"], "2026-01-01");
+
+        let results = get_json(&mut router, "/api/v1/search?q=Fumble").await;
+        assert!(!results.as_array().unwrap().is_empty());
+        let results = get_json(&mut router, "/api/v1/search").await;
+        assert!(results.as_array().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_is_day_available() {
+        let start_date = Date::constant(2026, 1, 1);
+        let current_date = Date::constant(2026, 1, 20);
+        let state = test_state(start_date, current_date, false);
+        assert!(!is_day_available(&state, Date::constant(2025, 12, 31)));
+        assert!(is_day_available(&state, start_date));
+        assert!(is_day_available(&state, current_date));
+        assert!(!is_day_available(&state, Date::constant(2026, 1, 21)));
+
+        let state = test_state(start_date, current_date, true);
+        assert!(!is_day_available(&state, Date::constant(2025, 12, 31)));
+        assert!(is_day_available(&state, Date::constant(2026, 1, 21)));
+        assert!(get_rule_for_date(&state, Date::constant(2026, 1, 21)).is_some());
+    }
+
+    #[tokio::test]
+    async fn test_get_day_rule() {
+        let state = test_state(
+            Date::constant(2026, 1, 1),
+            Date::constant(2026, 1, 20),
+            false,
+        );
+        let mut router = build_router(state.clone());
+
+        for (uri, status) in [
+            ("/day/2025-12-31", StatusCode::NOT_FOUND),
+            ("/day/2026-01-21", StatusCode::NOT_FOUND),
+            ("/day/yesterday", StatusCode::BAD_REQUEST),
+        ] {
+            assert_eq!(
+                send_request(&mut router, uri, None).await.status(),
+                status,
+                "{uri}"
+            );
+        }
+
+        // There is no link before the start date and after the current date
+        let first_day = get_text(&mut router, "/day/2026-01-01").await;
+        assert!(!first_day.contains("href=\"/day/2025-12-31\""));
+        assert!(first_day.contains("href=\"/day/2026-01-02\""));
+        let current_day = get_text(&mut router, "/day/2026-01-20").await;
+        assert!(current_day.contains("href=\"/day/2026-01-19\""));
+        assert!(!current_day.contains("href=\"/day/2026-01-21\""));
+        let rule = get_rule_for_date(&state, Date::constant(2026, 1, 20)).unwrap();
+        assert!(current_day.contains(&rule.to_title()));
+
+        let state = test_state(
+            Date::constant(2026, 1, 1),
+            Date::constant(2026, 1, 20),
+            true,
+        );
+        let mut router = build_router(state);
+        let current_day = get_text(&mut router, "/day/2026-01-20").await;
+        assert!(current_day.contains("href=\"/day/2026-01-21\""));
+        get_text(&mut router, "/day/2026-01-21").await;
+    }
+
+    #[tokio::test]
+    async fn test_get_archive() {
+        let archive_days = |archive: &str| archive.matches("<td><a href=\"/day/").count();
+
+        let state = test_state(
+            Date::constant(2026, 1, 1),
+            Date::constant(2026, 1, 20),
+            false,
+        );
+        let mut router = build_router(state);
+        // The archive ends with the start date
+        let archive = get_text(&mut router, "/archive").await;
+        assert_eq!(archive_days(&archive), 20);
+        assert!(archive.contains("href=\"/day/2026-01-20\""));
+        assert!(archive.contains("href=\"/day/2026-01-01\""));
+        assert_eq!(
+            archive_days(&get_text(&mut router, "/archive?days=5").await),
+            5
+        );
+
+        let state = test_state(
+            Date::constant(2024, 1, 1),
+            Date::constant(2026, 1, 20),
+            false,
+        );
+        let mut router = build_router(state);
+        let archive = get_text(&mut router, "/archive").await;
+        assert_eq!(archive_days(&archive), usize::from(ARCHIVE_DAYS));
+        assert!(!archive.contains("href=\"/day/2026-01-21\""));
+        assert_eq!(
+            archive_days(&get_text(&mut router, "/archive?days=1000").await),
+            usize::from(MAX_ARCHIVE_DAYS)
+        );
+        assert_eq!(
+            archive_days(&get_text(&mut router, "/archive?days=0").await),
+            0
+        );
+    }
+
+    #[tokio::test]
+    async fn test_rule_navigation() {
+        let state = test_state(
+            Date::constant(2026, 1, 1),
+            Date::constant(2026, 1, 20),
+            false,
+        );
+        let mut router = build_router(state.clone());
+
+        for (uri, status) in [
+            ("/chapter/neun", StatusCode::BAD_REQUEST),
+            ("/chapter/99", StatusCode::NOT_FOUND),
+            ("/section/9", StatusCode::BAD_REQUEST),
+            ("/section/9-x", StatusCode::BAD_REQUEST),
+            ("/section/9-99", StatusCode::NOT_FOUND),
+            ("/section/99-1", StatusCode::NOT_FOUND),
+        ] {
+            assert_eq!(
+                send_request(&mut router, uri, None).await.status(),
+                status,
+                "{uri}"
+            );
+        }
+        let link = |url: String| format!("href=\"{url}\"");
+
+        // A chapter links its sections and a section links its chapter and shows its rules
+        let chapter = state.chapters.iter().find(|c| c.chapter_nr == 9).unwrap();
+        let chapter_page = get_text(&mut router, "/chapter/9").await;
+        assert!(chapter_page.contains(&chapter.to_title()));
+        for section in &chapter.sections {
+            assert!(chapter_page.contains(&link(section.to_url(""))));
+        }
+        let section = &chapter.sections[0];
+        let section_page = get_text(&mut router, "/section/9-1").await;
+        assert!(section_page.contains(&link(chapter.to_url(""))));
+        let mut section_rules = section.rules(&state.rules).peekable();
+        assert!(section_rules.peek().is_some());
+        for rule in section_rules {
+            assert!(section_page.contains(&html! { (rule.to_title()) }.into_string()));
+        }
+
+        // A rule links its section and the previous and the next rule
+        let rule_page = |index| {
+            let (_, rule) = state.rules.get_index(index).unwrap();
+            rule.to_url("")
+        };
+        let page = get_text(&mut router, &rule_page(1)).await;
+        assert!(page.contains(&format!("{}>← ", link(rule_page(0)))));
+        assert!(page.contains(&link(rule_page(2))));
+        let (_, rule) = state.rules.get_index(1).unwrap();
+        let (_, section) = find_section(&state.chapters, rule.article_nr.section_nr()).unwrap();
+        assert!(page.contains(&link(section.to_url(""))));
+
+        // There is no rule before the first and after the last one
+        assert!(!get_text(&mut router, &rule_page(0)).await.contains("← "));
+        let last_page = get_text(&mut router, &rule_page(state.rules.len() - 1)).await;
+        assert!(last_page.contains(&format!("{}>← ", link(rule_page(state.rules.len() - 2)))));
+        assert!(!last_page.contains(" →"));
+    }
+
+    #[tokio::test]
+    async fn test_get_current_interpretation() {
+        let state = test_state(
+            Date::constant(2026, 1, 1),
+            Date::constant(2026, 1, 20),
+            false,
+        );
+        let mut router = build_router(state.clone());
+
+        // The interpretation of the day is the current item of the interpretations rotation
+        let entry = state.dynamic_state.read().unwrap().rotations[INTERPRETATION_ROTATION]
+            .schedule
+            .get_entry(Date::constant(2026, 1, 20))
+            .unwrap();
+        let Some(RotationContent::Interpretation(_, interpretation)) = entry.resolve(&state.rules)
+        else {
+            panic!("Not an interpretation: {entry:?}");
+        };
+        let response = send_request(
+            &mut router,
+            "/interpretations/today",
+            Some("application/json"),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        assert_eq!(
+            serde_json::from_slice::<Value>(&body).unwrap(),
+            serde_json::to_value(interpretation).unwrap()
+        );
+        let page = get_text(&mut router, "/interpretations/today").await;
+        assert!(page.contains(&interpretation.get_title()));
+    }
+
+    #[tokio::test]
+    async fn test_calendar() {
+        let state = test_state(
+            Date::constant(2026, 1, 1),
+            Date::constant(2026, 1, 3),
+            false,
+        );
+        let mut router = build_router(state);
+
+        // Without --allow-future-days the calendar ends today, there are no days before the start date
+        let calendar = get_text(&mut router, "/calendar.ics?days_before=7&days_after=5").await;
+        assert_eq!(calendar.matches("BEGIN:VEVENT\r\n").count(), 3);
+        assert!(calendar.contains("DTSTART;VALUE=DATE:20260101\r\n"));
+        assert!(calendar.contains("DTSTART;VALUE=DATE:20260103\r\n"));
+        assert!(!calendar.contains("DTSTART;VALUE=DATE:20260104\r\n"));
+
+        let state = test_state(Date::constant(2026, 1, 1), Date::constant(2026, 1, 3), true);
+        let mut router = build_router(state);
+        let calendar = get_text(&mut router, "/calendar.ics?days_before=7&days_after=5").await;
+        assert_eq!(calendar.matches("BEGIN:VEVENT\r\n").count(), 8);
+        assert!(calendar.contains("DTSTART;VALUE=DATE:20260108\r\n"));
+    }
+
+    #[tokio::test]
+    async fn test_content_negotiation() {
+        let state = test_state(
+            Date::constant(2026, 1, 1),
+            Date::constant(2026, 1, 20),
+            false,
+        );
+        let mut router = build_router(state);
+
+        for uri in ["/", "/all", "/rule/9-1-10", "/interpretations"] {
+            let response =
+                send_request(&mut router, uri, Some("text/html;q=0.1, application/json")).await;
+            assert_eq!(response.status(), StatusCode::OK, "{uri}");
+            assert_eq!(response.headers()[header::VARY], "accept", "{uri}");
+            assert_eq!(
+                response.headers()[header::CONTENT_TYPE],
+                "application/json",
+                "{uri}"
+            );
+
+            let response = send_request(&mut router, uri, Some("text/html")).await;
+            assert_eq!(response.headers()[header::VARY], "accept", "{uri}");
+            assert!(
+                response.headers()[header::CONTENT_TYPE]
+                    .to_str()
+                    .unwrap()
+                    .starts_with("text/html"),
+                "{uri}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_build_rss() {
+        let rules = parsed_rules_fixture();
+        let start_date = Date::constant(2026, 1, 1);
+        let current_date = Date::constant(2026, 1, 20);
+        let rotation = default_rotation(start_date);
+        let mut schedule = Schedule::load_for_rules(None, &rotation, rules).unwrap();
+        schedule.publish_until(current_date, rules).unwrap();
+        let history = get_feed_history(&schedule, current_date, rules);
+
+        let channel =
+            rss::Channel::read_from(build_rss(&rotation, current_date, &history).as_bytes())
+                .unwrap();
+        assert_eq!(channel.items().len(), FEED_DAYS);
+        let item = &channel.items()[0];
+        assert_eq!(item.pub_date(), Some("Tue, 20 Jan 2026 00:00:00 +0100"));
+        assert_eq!(
+            item.guid().unwrap().value(),
+            "https://ruleoftheday.de/day/2026-01-20"
+        );
+        let description = item.description().unwrap();
+        assert!(description.contains("Offizielles Regelwerk"));
+        assert!(!description.contains("href=\"/"));
+
+        let feed: atom_syndication::Feed = build_atom(&rotation, current_date, &history)
+            .parse()
+            .unwrap();
+        assert_eq!(feed.entries().len(), FEED_DAYS);
+        assert_eq!(feed.entries()[0].id(), item.guid().unwrap().value());
+        assert_eq!(
+            feed.entries()[0].updated().to_rfc3339(),
+            "2026-01-20T00:00:00+01:00"
+        );
+
+        let json_feed: serde_json::Value =
+            serde_json::from_str(&build_json_feed(&rotation, &history)).unwrap();
+        assert_eq!(json_feed["items"][0]["id"], item.guid().unwrap().value());
+        assert_eq!(
+            json_feed["items"][0]["date_published"],
+            "2026-01-20T00:00:00+01:00"
+        );
+
+        // The items of previous days stay the same and the history ends with the start date
+        let current_date = Date::constant(2026, 1, 3);
+        let history = get_feed_history(&schedule, current_date, rules);
+        let channel =
+            rss::Channel::read_from(build_rss(&rotation, current_date, &history).as_bytes())
+                .unwrap();
+        assert_eq!(channel.items().len(), 3);
+        assert_eq!(
+            channel.items()[2].guid().unwrap().value(),
+            "https://ruleoftheday.de/day/2026-01-01"
+        );
+
+        // Rotations of interpretations have their own feed
+        let rotation = RotationConfig::interpretation_of_the_day(start_date);
+        let mut schedule = Schedule::load_for_rules(None, &rotation, rules).unwrap();
+        schedule.publish_until(current_date, rules).unwrap();
+        let history = get_feed_history(&schedule, current_date, rules);
+        let channel =
+            rss::Channel::read_from(build_rss(&rotation, current_date, &history).as_bytes())
+                .unwrap();
+        assert_eq!(
+            channel.link(),
+            format!("https://ruleoftheday.de/rotation/{}", rotation.name)
+        );
+        let item = &channel.items()[0];
+        assert!(item.title().unwrap().starts_with("A.R. "));
+        assert!(item.link().unwrap().contains("/interpretation/"));
+        assert_eq!(
+            item.guid().unwrap().value(),
+            format!(
+                "https://ruleoftheday.de/rotation/{}/day/2026-01-03",
+                rotation.name
+            )
+        );
+    }
+
+    #[test]
+    fn test_build_calendar() {
+        let rules = parsed_rules_fixture();
+        let start_date = Date::constant(2026, 1, 1);
+        let rotation = default_rotation(start_date);
+        let schedule = Schedule::load_for_rules(None, &rotation, rules).unwrap();
+        let days: Vec<_> = start_date
+            .series(Span::new().days(1))
+            .take(31)
+            .map(|date| (date, get_scheduled_rule(&schedule, rules, date).unwrap()))
+            .collect();
+
+        let timestamp: Timestamp = "2026-01-15T08:30:00+01:00".parse().unwrap();
+        let calendar = build_calendar(&days, timestamp);
+        assert!(calendar.starts_with("BEGIN:VCALENDAR\r\n"));
+        assert!(calendar.ends_with("END:VCALENDAR\r\n"));
+        assert_eq!(calendar.matches("BEGIN:VEVENT\r\n").count(), 31);
+        assert!(calendar.contains("DTSTART;VALUE=DATE:20260131\r\nDTEND;VALUE=DATE:20260201\r\n"));
+        assert!(calendar.contains(&format!("URL:{}\r\n", days[0].1.to_url(PUB_URL))));
+        // The stamp is the time of the creation in UTC and not the date of the event
+        assert_eq!(calendar.matches("DTSTAMP:20260115T073000Z\r\n").count(), 31);
+        assert!(calendar.split("\r\n").all(|line| line.len() <= 75));
+        assert!(!calendar.replace("\r\n", "").contains('\n'));
+    }
+
+    #[test]
+    fn test_get_random_rule() {
+        let rules = parsed_rules_fixture();
+
+        // Every rule can be drawn, also the last one
+        let two_rules: IndexMap<_, _> =
+            rules.iter().take(2).map(|(a, r)| (*a, r.clone())).collect();
+        let drawn: HashSet<_> = (0..50)
+            .map(|seed| {
+                let params = RandomParams {
+                    seed: Some(seed.to_string()),
+                    ..Default::default()
+                };
+                get_random_rule(&two_rules, &params).unwrap().article_nr
+            })
+            .collect();
+        assert_eq!(drawn.len(), 2);
+
+        let params = RandomParams {
+            chapter: Some(9),
+            with_interpretations: Some(true),
+            changed: Some(true),
+            seed: Some("share".to_string()),
+        };
+        let rule = get_random_rule(rules, &params).unwrap();
+        assert_eq!(rule.article_nr.0, 9);
+        assert!(!rule.interpretations.is_empty());
+        assert!(rule.changed_this_season);
+        assert_eq!(
+            get_random_rule(rules, &params).unwrap().article_nr,
+            rule.article_nr
+        );
+
+        let params = RandomParams {
+            chapter: Some(99),
+            ..Default::default()
+        };
+        assert!(get_random_rule(rules, &params).is_none());
+    }
+
+    #[test]
+    fn test_openapi() {
+        let mut openapi = ApiDoc::openapi();
+        // Keep the snapshot stable across releases
+        openapi.info.version = "[version]".to_string();
+        let document = serde_json::to_value(&openapi).unwrap();
+
+        let mut refs = vec![];
+        collect_refs(&document, &mut refs);
+        assert!(!refs.is_empty());
+        for reference in refs {
+            let name = reference.trim_start_matches("#/components/schemas/");
+            assert!(
+                document["components"]["schemas"].get(name).is_some(),
+                "Missing schema {reference}"
+            );
+        }
+
+        assert_yaml_snapshot!("openapi", openapi);
+    }
 }
@@ -1,20 +1,52 @@
-use eyre::{Context, eyre};
+use eyre::eyre;
 use indexmap::IndexMap;
 use once_cell::sync::Lazy;
 use regex::{Captures, Regex};
 use roman_numerals::FromRoman;
-use std::{path::Path, process::Command};
+use std::{
+    collections::{HashSet, VecDeque},
+    path::Path,
+};
 
-use crate::rule::{ArticleNr, Rule, RuleInterpretation};
+use crate::{
+    body::{ListItem, ListStyle, RuleBlock, RuleList},
+    change::{
+        InterpretationChange, InterpretationChangeKind, RuleChange, RuleChangeKind, SeasonChanges,
+    },
+    chapter::{Chapter, Section},
+    extractor::TextExtractor,
+    penalty::{EnforcementSpot, Penalty, PenaltySummary, PenaltySummaryEntry},
+    profile::EditionProfile,
+    reference::{Reference, find_references},
+    rule::{ArticleNr, Rule, RuleInterpretation, SectionNr},
+    signal::Signal,
+    standard::{Standard, StandardNr, StandardSection},
+};
 
-pub struct RulesParser;
+#[derive(Default)]
+pub struct RulesParser {
+    profile: EditionProfile,
+}
 
 impl RulesParser {
-    pub fn parse(rules_path: &Path) -> eyre::Result<IndexMap<ArticleNr, Rule>> {
-        let rules_text = Self::load_rules_text(rules_path)?;
+    pub fn new(profile: EditionProfile) -> Self {
+        Self { profile }
+    }
+
+    pub fn parse(
+        &self,
+        rules_path: &Path,
+        extractor: &dyn TextExtractor,
+    ) -> eyre::Result<IndexMap<ArticleNr, Rule>> {
+        self.parse_text(extractor.extract_text(rules_path)?)
+    }
 
-        let mut rules = Self::extract_rules(&rules_text)?;
-        let interpretations = Self::extract_interpretations(&rules_text)?;
+    pub fn parse_text(&self, rules_text: String) -> eyre::Result<IndexMap<ArticleNr, Rule>> {
+        let changes = self.extract_changes(&rules_text)?;
+        let rules_text = self.preprocess_text(rules_text);
+
+        let mut rules = self.extract_rules(&rules_text)?;
+        let interpretations = self.extract_interpretations(&rules_text)?;
 
         for (article_nr, article_interpretations) in interpretations {
             let rule = rules
@@ -23,65 +55,96 @@ impl RulesParser {
             rule.interpretations = article_interpretations;
         }
 
+        for change in &changes.rules {
+            rules
+                .get_mut(&change.rule_ref.article_nr)
+                .ok_or_else(|| eyre!("Could not find changed rule {}", change.rule_ref))?
+                .changed_this_season = true;
+        }
+        for change in &changes.interpretations {
+            rules
+                .get_mut(&change.article_nr)
+                .and_then(|r| {
+                    r.interpretations
+                        .iter_mut()
+                        .find(|i| i.index == change.index)
+                })
+                .ok_or_else(|| {
+                    eyre!(
+                        "Could not find changed interpretation {}",
+                        change.get_title()
+                    )
+                })?
+                .changed_this_season = true;
+        }
+
+        add_backlinks(&mut rules);
+
         Ok(rules)
     }
 
+    pub fn parse_changes(
+        &self,
+        rules_path: &Path,
+        extractor: &dyn TextExtractor,
+    ) -> eyre::Result<SeasonChanges> {
+        self.extract_changes(&extractor.extract_text(rules_path)?)
+    }
+
     pub fn parse_interpretations(
+        &self,
         rules_path: &Path,
+        extractor: &dyn TextExtractor,
     ) -> eyre::Result<IndexMap<ArticleNr, Vec<RuleInterpretation>>> {
-        let rules_text = Self::load_rules_text(rules_path)?;
-
-        Self::extract_interpretations(&rules_text)
-    }
-
-    fn load_rules_text(rules_path: &Path) -> eyre::Result<String> {
-        let rules_text = Self::extract_text_from_pdf(rules_path)?;
-        Ok(Self::preprocess_text(rules_text))
-    }
-
-    fn extract_text_from_pdf(rules_path: &Path) -> eyre::Result<String> {
-        let pdftotext_output = Command::new("pdftotext")
-            .args([
-                "-x",
-                "0",
-                "-y",
-                "40",
-                "-W",
-                "1000",
-                "-H",
-                "540",
-                rules_path
-                    .to_str()
-                    .ok_or(eyre!("Invalid path to rules pdf"))?,
-                "-",
-            ])
-            .output()
-            .wrap_err("Could not run pdftotext")?;
-        if !pdftotext_output.status.success() {
-            return Err(eyre!(
-                "pdftotext did not exit successfuly: {}",
-                String::from_utf8(pdftotext_output.stderr)?
-            ));
-        }
+        let rules_text = self.load_rules_text(rules_path, extractor)?;
 
-        String::from_utf8(pdftotext_output.stdout).wrap_err("Stdout is no valid utf8")
+        self.extract_interpretations(&rules_text)
     }
 
-    fn preprocess_text(mut text: String) -> String {
-        // Treat section 9.1. as rule
-        text = text.replace(
-            "Abschnitt 9.1 Persönliche Fouls\nAlle",
-            "Artikel 9.1.0 Persönliche Fouls\nAlle",
-        );
-        text = text.replace("Regel 9\nVerhalten von Spielern und anderen", "");
-
-        // Fixes for rules that are longer than one line
-        text = text.replace("9.1.4 Targeting und Forcible Contact zum Kopf-/Halsbereich\nverteidigungsloser Spieler",
-                                        "9.1.4 Targeting und Forcible Contact zum Kopf-/Halsbereich verteidigungsloser Spieler");
-        text = text.replace(
-            "6.1.3 Berühren, illegales Berühren und Recovern eines Free\nKicks",
-            "6.1.3 Berühren, illegales Berühren und Recovern eines Free Kicks",
-        );
+    pub fn parse_penalties(
+        &self,
+        rules_path: &Path,
+        extractor: &dyn TextExtractor,
+    ) -> eyre::Result<PenaltySummary> {
+        // The columns of the table get mixed up when pages are merged, so the raw text is used
+        self.extract_penalty_summary(&extractor.extract_text(rules_path)?)
+    }
+
+    pub fn parse_signals(
+        &self,
+        rules_path: &Path,
+        extractor: &dyn TextExtractor,
+    ) -> eyre::Result<IndexMap<u8, Signal>> {
+        self.extract_signals(&extractor.extract_text(rules_path)?)
+    }
+
+    pub fn parse_standards(
+        &self,
+        rules_path: &Path,
+        extractor: &dyn TextExtractor,
+    ) -> eyre::Result<Vec<StandardSection>> {
+        // Merging the sections of part I would swallow part II, so the raw text is used
+        self.extract_standards(&extractor.extract_text(rules_path)?)
+    }
+
+    fn load_rules_text(
+        &self,
+        rules_path: &Path,
+        extractor: &dyn TextExtractor,
+    ) -> eyre::Result<String> {
+        let rules_text = extractor.extract_text(rules_path)?;
+        Ok(self.preprocess_text(rules_text))
+    }
+
+    fn apply_replacements(&self, mut text: String) -> String {
+        for replacement in &self.profile.replacements {
+            text = text.replace(&replacement.from, &replacement.to);
+        }
+        text
+    }
+
+    pub fn preprocess_text(&self, text: String) -> String {
+        let mut text = self.apply_replacements(text);
 
         let re_new_page = Regex::new(r"-?\n\x0C").unwrap();
         let re_new_page_inner = Regex::new(r"-\n\n\x0C").unwrap();
@@ -96,15 +159,10 @@ impl RulesParser {
         text
     }
 
-    fn extract_rules(text: &str) -> eyre::Result<IndexMap<ArticleNr, Rule>> {
-        let rules_start = text
-            .find("Artikel 1.1.1")
-            .ok_or(eyre!("Could not find 'Artikel 1.1.1' inside the pdf text"))?;
-        let rules_end = text
-            .find("Die Abkürzungen R, Ab, Art stehen für Regel,")
-            .ok_or(eyre!(
-                "Could not find 'Die Abkürzungen R, Ab, Art stehen für Regel,' inside the pdf text"
-            ))?;
+    pub fn extract_rules(&self, text: &str) -> eyre::Result<IndexMap<ArticleNr, Rule>> {
+        let markers = &self.profile.markers;
+        let rules_start = find_marker(text, &markers.rules_start)?;
+        let rules_end = find_marker(text, &markers.rules_end)?;
 
         let mut rules = IndexMap::new();
 
@@ -119,7 +177,7 @@ impl RulesParser {
 
         for (article_header, next) in captures.iter().zip(captures.iter().skip(1)) {
             last_captures = Some(next);
-            let rule = Self::extract_rule_from_text(
+            let rule = self.extract_rule_from_text(
                 rules_part,
                 article_header,
                 next.get(0).unwrap().start(),
@@ -129,36 +187,40 @@ impl RulesParser {
         if let Some(last_capture) = last_captures {
             let end_of_last_capture = last_capture.get(0).unwrap().end();
             let rules_end = rules_part[end_of_last_capture..]
-                .find("Zusammenfassung der Strafen")
+                .find(&markers.last_rule_end)
                 .ok_or(eyre!("Could not find end of rules"))?
                 + end_of_last_capture;
-            let rule = Self::extract_rule_from_text(rules_part, last_capture, rules_end)?;
+            let rule = self.extract_rule_from_text(rules_part, last_capture, rules_end)?;
             rules.insert(rule.article_nr, rule);
         }
         Ok(rules)
     }
 
-    fn extract_interpretations(
+    pub fn extract_interpretations(
+        &self,
         text: &str,
     ) -> eyre::Result<IndexMap<ArticleNr, Vec<RuleInterpretation>>> {
-        let re_rule = Regex::new(r"(?sm)^Regel .*?A\.R\.").unwrap();
-        let re_section = Regex::new(r"(?sm)^Abschnitt .*?A\.R\.").unwrap();
-        let re_article = Regex::new(r"(?sm)^Artikel .*?A\.R\.").unwrap();
-
-        let mut text = re_rule.replace_all(text, "\nA.R.").to_string();
-        text = re_section.replace_all(&text, "\nA.R.").to_string();
-        text = re_section.replace_all(&text, "\nA.R.").to_string();
-        text = re_article.replace_all(&text, "\nA.R.").to_string();
+        let mut text = text.to_string();
+        if !self.profile.interpretation_headings.is_empty() {
+            let headings = self
+                .profile
+                .interpretation_headings
+                .iter()
+                .map(|h| regex::escape(h))
+                .collect::<Vec<_>>()
+                .join("|");
+            let re_heading = Regex::new(&format!(r"(?sm)^(?:{headings}) .*?A\.R\.")).unwrap();
+            text = re_heading.replace_all(&text, "\nA.R.").to_string();
+        }
 
-        text = text.replace("Rege-\nlung", "Regelung");
+        for replacement in &self.profile.interpretation_replacements {
+            text = text.replace(&replacement.from, &replacement.to);
+        }
         text = text.replace("-\n", "\n");
 
-        let interpretations_start = text.find("\nA.R. 1.3.2.I ").ok_or(eyre!(
-            "Could not find '\\nA.R. 1.3.2.I ' inside the pdf text"
-        ))?;
-        let interpretations_end = text
-            .find("Teil IV")
-            .ok_or(eyre!("Could not find 'Teil IV' inside the pdf text"))?;
+        let markers = &self.profile.markers;
+        let interpretations_start = find_marker(&text, &markers.interpretations_start)?;
+        let interpretations_end = find_marker(&text, &markers.interpretations_end)?;
 
         let mut interpretations: IndexMap<ArticleNr, Vec<RuleInterpretation>> = IndexMap::new();
 
@@ -172,6 +234,8 @@ impl RulesParser {
             re_interpretation.captures_at(&interpretations_text, current_position)
         {
             let article_nr = captures["ar_nr"].parse()?;
+            let text = captures["situation"].trim().to_string();
+            let ruling = captures["ruling"].trim().to_string();
             interpretations
                 .entry(article_nr)
                 .or_default()
@@ -179,8 +243,10 @@ impl RulesParser {
                     article_nr,
                     index: u8::from_roman(&captures["index"])
                         .ok_or_else(|| eyre!("Invalid Roman number."))?,
-                    text: captures["situation"].trim().to_string(),
-                    ruling: captures["ruling"].trim().to_string(),
+                    references: find_references(&format!("{text}\n{ruling}")),
+                    text,
+                    ruling,
+                    changed_this_season: false,
                 });
             current_position = captures.get(0).unwrap().end() - 5;
         }
@@ -188,7 +254,334 @@ impl RulesParser {
         Ok(interpretations)
     }
 
+    pub fn extract_penalty_summary(&self, text: &str) -> eyre::Result<PenaltySummary> {
+        static RE_SPOTS: Lazy<Regex> = Lazy::new(|| {
+            Regex::new(r"^(?<spots>[PSFB](?:,[PSFB])*)(?: (?<description>.+))?$").unwrap()
+        });
+        static RE_COLUMN_VALUES: Lazy<Regex> =
+            Lazy::new(|| Regex::new(r"^(?:-|\d+(?:,? ?\d+)*,?)$").unwrap());
+
+        const COLUMN_HEADERS: [&str; 6] = ["Signal", "R", "Ab", "Art", "Ab Art", "R Ab Art"];
+
+        let markers = &self.profile.markers;
+        let summary_start = find_marker(text, &markers.penalty_summary_start)?;
+        let summary_end =
+            find_marker(&text[summary_start..], &markers.penalty_summary_end)? + summary_start;
+        let summary_text = text[summary_start..summary_end].replace('\x0C', "");
+        let lines: Vec<&str> = summary_text.lines().map(str::trim).collect();
+
+        // pdftotext outputs the descriptions and the columns with the numbers as separate streams
+        let mut descriptions = DescriptionColumn::default();
+        let mut column_groups: Vec<ColumnGroup> = vec![];
+        let mut column_header = None;
+        let mut in_column_group = false;
+
+        let mut i = 0;
+        while i < lines.len() {
+            let line = lines[i];
+            let next_line = lines.get(i + 1).copied().unwrap_or_default();
+            i += 1;
+
+            if line.is_empty() {
+                descriptions.finish_entry();
+                in_column_group = false;
+            } else if matches!(next_line, "Spot Bezeichnung" | "Bezeichnung") {
+                descriptions.finish_entry();
+                if !line.ends_with(", Fortsetzung") {
+                    descriptions.category = Some(line.to_string());
+                }
+                i += 1;
+            } else if COLUMN_HEADERS.contains(&line) {
+                descriptions.finish_entry();
+                in_column_group = false;
+                column_header = Some(line);
+            } else if RE_COLUMN_VALUES.is_match(line) {
+                let mut values = line.to_string();
+                if line.ends_with(',') {
+                    values = format!("{values} {next_line}");
+                    i += 1;
+                }
+                if !in_column_group {
+                    column_groups.push(ColumnGroup {
+                        header: column_header.take(),
+                        values: vec![],
+                    });
+                    in_column_group = true;
+                }
+                column_groups.last_mut().unwrap().values.push(values);
+            } else if let Some(captures) = RE_SPOTS.captures(line) {
+                descriptions.finish_entry();
+                descriptions.spots.push_back(
+                    captures["spots"]
+                        .split(',')
+                        .map(str::parse)
+                        .collect::<eyre::Result<_>>()?,
+                );
+                if let Some(description) = captures.name("description") {
+                    descriptions.lines.push(description.as_str().to_string());
+                    if RE_DOT_LEADERS.is_match(line) {
+                        descriptions.finish_entry();
+                    }
+                }
+            } else {
+                descriptions.lines.push(line.to_string());
+                if RE_DOT_LEADERS.is_match(line) {
+                    descriptions.finish_entry();
+                }
+            }
+        }
+        descriptions.finish_entry();
+
+        let (signals, references) = assemble_penalty_columns(&column_groups)?;
+        if descriptions.entries.len() != references.len() {
+            return Err(eyre!(
+                "Found {} penalties but {} references in the penalty summary",
+                descriptions.entries.len(),
+                references.len()
+            ));
+        }
+
+        let entries = descriptions
+            .entries
+            .into_iter()
+            .zip(signals)
+            .zip(references)
+            .map(
+                |(((category, enforcement_spots, description), signals), article_nr)| {
+                    PenaltySummaryEntry::new(
+                        category,
+                        description,
+                        enforcement_spots,
+                        signals,
+                        article_nr,
+                        &self.profile.automatic_first_down_phrases,
+                    )
+                },
+            )
+            .collect();
+
+        Ok(PenaltySummary { entries })
+    }
+
+    pub fn extract_signals(&self, text: &str) -> eyre::Result<IndexMap<u8, Signal>> {
+        static RE_SIGNAL_NR: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?m)^\x0C*(\d+)$").unwrap());
+
+        let markers = &self.profile.markers;
+        let signals_start = find_marker(text, &markers.signals_start)?;
+        let signals_end =
+            find_marker(&text[signals_start..], &markers.signals_end)? + signals_start;
+        let signals_text = &text[signals_start..signals_end];
+
+        let signal_nrs: HashSet<u8> = RE_SIGNAL_NR
+            .captures_iter(signals_text)
+            .filter_map(|c| c[1].parse().ok())
+            .collect();
+        let last_signal_nr = *signal_nrs
+            .iter()
+            .max()
+            .ok_or_else(|| eyre!("Could not find any signal numbers"))?;
+
+        // Captions are broken into multiple lines
+        let compact_text: String = signals_text.split_whitespace().collect();
+        (1..=last_signal_nr)
+            .map(|nr| {
+                if let Some(signal) = self.profile.signals.iter().find(|s| s.nr == nr) {
+                    let caption: String = signal.name.split_whitespace().collect();
+                    if !compact_text.contains(&caption) {
+                        return Err(eyre!("Could not find signal {}", signal.to_title()));
+                    }
+                    return Ok((nr, signal.clone()));
+                }
+                self.profile
+                    .transcribed_signals
+                    .iter()
+                    .find(|s| s.nr == nr)
+                    .map(|signal| (nr, signal.clone()))
+                    .ok_or_else(|| eyre!("Signal S{nr} has no name in the profile"))
+            })
+            .collect()
+    }
+
+    pub fn extract_changes(&self, text: &str) -> eyre::Result<SeasonChanges> {
+        static RE_INTERPRETATION: Lazy<Regex> =
+            Lazy::new(|| Regex::new(r"^(?<article_nr>\d+\.\d+\.\d+)\.(?<index>[IVX]+)$").unwrap());
+
+        // The interpretation changes are removed together with the section headers by preprocessing
+        let text = self
+            .apply_replacements(text.to_string())
+            .replace('\x0C', "");
+        let markers = &self.profile.markers;
+
+        let rule_changes_start = find_marker(&text, &markers.rule_changes_start)?;
+        let editorial_changes_start = find_marker(
+            &text[rule_changes_start..],
+            &markers.editorial_changes_start,
+        )? + rule_changes_start;
+        let rule_changes_end =
+            find_marker(&text[editorial_changes_start..], &markers.rule_changes_end)?
+                + editorial_changes_start;
+
+        let mut rules = extract_rule_changes(
+            &text[rule_changes_start..editorial_changes_start],
+            RuleChangeKind::Rule,
+        )?;
+        rules.extend(extract_rule_changes(
+            &text[editorial_changes_start..rule_changes_end],
+            RuleChangeKind::Editorial,
+        )?);
+
+        let new_interpretations_start = find_marker(&text, &markers.new_interpretations_start)?;
+        let changed_interpretations_start = find_marker(
+            &text[new_interpretations_start..],
+            &markers.changed_interpretations_start,
+        )? + new_interpretations_start;
+        let interpretation_changes_end = find_marker(
+            &text[changed_interpretations_start..],
+            &markers.interpretation_changes_end,
+        )? + changed_interpretations_start;
+
+        let mut interpretations = vec![];
+        for (kind, changes_text) in [
+            (
+                InterpretationChangeKind::New,
+                &text[new_interpretations_start..changed_interpretations_start],
+            ),
+            (
+                InterpretationChangeKind::Changed,
+                &text[changed_interpretations_start..interpretation_changes_end],
+            ),
+        ] {
+            for captures in changes_text
+                .lines()
+                .filter_map(|l| RE_INTERPRETATION.captures(l.trim()))
+            {
+                interpretations.push(InterpretationChange {
+                    kind,
+                    article_nr: captures["article_nr"].parse()?,
+                    index: u8::from_roman(&captures["index"])
+                        .ok_or_else(|| eyre!("Invalid Roman number."))?,
+                });
+            }
+        }
+
+        Ok(SeasonChanges {
+            rules,
+            interpretations,
+        })
+    }
+
+    pub fn extract_standards(&self, text: &str) -> eyre::Result<Vec<StandardSection>> {
+        static RE_SECTION_HEADER: Lazy<Regex> = Lazy::new(|| {
+            Regex::new(r"(?m)^Abschnitt (?<chapter>\d+)\.(?<section>\d+) (?<title>.*)$").unwrap()
+        });
+        static RE_ITEM: Lazy<Regex> = Lazy::new(|| Regex::new(r"^(?<index>\d+)\. ").unwrap());
+
+        let markers = &self.profile.markers;
+        let standards_start = find_marker(text, &markers.standards_start)?;
+        let standards_end =
+            find_marker(&text[standards_start..], &markers.standards_end)? + standards_start;
+        let standards_text = text[standards_start..standards_end].replace('\x0C', "");
+
+        let headers: Vec<_> = RE_SECTION_HEADER.captures_iter(&standards_text).collect();
+        let mut sections = vec![];
+
+        for (i, header) in headers.iter().enumerate() {
+            let section_nr = SectionNr(header["chapter"].parse()?, header["section"].parse()?);
+            let title = header["title"].trim().to_string();
+            let section_end = headers
+                .get(i + 1)
+                .map_or(standards_text.len(), |next| next.get(0).unwrap().start());
+
+            let mut introduction: Vec<&str> = vec![];
+            let mut items: Vec<Vec<&str>> = vec![];
+            for line in standards_text[header.get(0).unwrap().end()..section_end]
+                .lines()
+                .map(str::trim)
+                .filter(|l| !l.is_empty())
+            {
+                // Lines that start with a number are only items if the numbering continues
+                let is_next_item = RE_ITEM
+                    .captures(line)
+                    .is_some_and(|c| c["index"].parse() == Ok(items.len() + 1));
+                match items.last_mut() {
+                    _ if is_next_item => items.push(vec![line]),
+                    Some(item) => item.push(line),
+                    None => introduction.push(line),
+                }
+            }
+
+            if items.is_empty() {
+                return Err(eyre!("Section {section_nr} of the standards has no items"));
+            }
+
+            let standards = items
+                .into_iter()
+                .enumerate()
+                .map(|(index, lines)| Standard {
+                    standard_nr: StandardNr(section_nr.0, section_nr.1, index as u8 + 1),
+                    section_title: title.clone(),
+                    text: RE_ITEM.replace(&lines.join(" "), "").to_string(),
+                })
+                .collect();
+
+            sections.push(StandardSection {
+                section_nr,
+                title,
+                introduction: (!introduction.is_empty()).then(|| introduction.join(" ")),
+                standards,
+            });
+        }
+
+        Ok(sections)
+    }
+
+    pub fn extract_chapters(&self, text: &str) -> eyre::Result<Vec<Chapter>> {
+        static RE_HEADING: Lazy<Regex> = Lazy::new(|| {
+            Regex::new(
+                r"(?m)^\x0C?(?:Regel (?<chapter>\d+)\n(?<chapter_title>.*)|Abschnitt (?<section_chapter>\d+)\.(?<section>\d+) (?<section_title>.*))$",
+            )
+            .unwrap()
+        });
+
+        // The headings are removed by preprocessing, so the raw text is used
+        let mut text = text.to_string();
+        for replacement in &self.profile.heading_replacements {
+            text = text.replace(&replacement.from, &replacement.to);
+        }
+        let rules_end = find_marker(&text, &self.profile.markers.rules_end)?;
+
+        let mut chapters: Vec<Chapter> = vec![];
+        for heading in RE_HEADING.captures_iter(&text[..rules_end]) {
+            if let Some(chapter_nr) = heading.name("chapter") {
+                chapters.push(Chapter {
+                    chapter_nr: chapter_nr.as_str().parse()?,
+                    title: heading["chapter_title"].trim().to_string(),
+                    sections: vec![],
+                });
+                continue;
+            }
+            let section_nr = SectionNr(
+                heading["section_chapter"].parse()?,
+                heading["section"].parse()?,
+            );
+            let chapter = chapters
+                .last_mut()
+                .filter(|c| c.chapter_nr == section_nr.0)
+                .ok_or_else(|| eyre!("Section {section_nr} is outside of its chapter"))?;
+            chapter.sections.push(Section {
+                section_nr,
+                title: heading["section_title"].trim().to_string(),
+            });
+        }
+
+        if chapters.is_empty() {
+            return Err(eyre!("Could not find any chapter headings"));
+        }
+        Ok(chapters)
+    }
+
     fn extract_rule_from_text(
+        &self,
         text: &str,
         article_header: &Captures,
         next_start: usize,
@@ -206,7 +599,7 @@ impl RulesParser {
         static RE_TRAILING_NUM: Lazy<Regex> = Lazy::new(|| Regex::new(r" \d+$").unwrap());
 
         let article_nr: ArticleNr = article_header["article_nr"].parse()?;
-        let title = article_header["title"].parse()?;
+        let title: String = article_header["title"].parse()?;
 
         let article_text = &text[article_header.get(0).unwrap().start()..next_start];
 
@@ -215,8 +608,7 @@ impl RulesParser {
             .ok_or(eyre!("No newline after header"))?;
         let mut text = article_text[text_start..].to_string();
 
-        // Special handling for Clipping and Blocking in the back
-        if (title == "Clipping" && article_nr.0 == 9) || title == "Blocken in den Rücken" {
+        if self.profile.has_nested_alpha_listing(article_nr, &title) {
             text = RE_NEWLINE_ALPHA_LISTING
                 .replace_all(&text, "\n\t\t\t$1")
                 .to_string();
@@ -226,9 +618,8 @@ impl RulesParser {
                 .to_string();
         }
 
-        // Special handling for 3.5.2
-        if article_nr == ArticleNr(3, 5, 2) {
-            text = text.replace("c)\n", "\n\tc) \n");
+        for replacement in &self.profile.rule_replacements {
+            text = replacement.apply(article_nr, text);
         }
 
         text = RE_NEWLINE_NUM_LISTING
@@ -247,58 +638,451 @@ impl RulesParser {
 
         text = RE_TRAILING_NUM.replace_all(&text, "").to_string();
 
-        // Special replacements for Targeting rule
-        text = text.replace("Anmerkung 1 Targeting", "\nAnmerkung 1\nTargeting");
-        text = text.replace(
-            "Anmerkung 2 Verteidigungslose",
-            "\nAnmerkung 2\nVerteidigungslose",
-        );
+        for replacement in &self.profile.rule_text_replacements {
+            text = replacement.apply(article_nr, text);
+        }
 
         if text.starts_with("a)") {
             text = format!("\t{text}");
         }
 
+        let penalties = extract_penalties(&text, &self.profile.automatic_first_down_phrases);
+        let body = build_rule_body(&text);
+        let references = find_references(&text);
+
         Ok(Rule {
             article_nr,
             title,
             text,
+            body,
+            penalties,
             interpretations: vec![],
+            references,
+            referenced_by: vec![],
+            changed_this_season: false,
         })
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use crate::RULE_BOOK_URL;
+fn extract_rule_changes(text: &str, kind: RuleChangeKind) -> eyre::Result<Vec<RuleChange>> {
+    static RE_REFERENCE: Lazy<Regex> = Lazy::new(|| {
+        Regex::new(r"^(?<rule_ref>\d+\.\d+\.\d+(?:\.[a-z0-9]+)*)(?: (?<remark>\S+))?$").unwrap()
+    });
 
-    use super::*;
+    // The references are followed by a column with one description per line
+    let lines: Vec<&str> = text.lines().map(str::trim).collect();
+    let first_reference = lines
+        .iter()
+        .position(|l| RE_REFERENCE.is_match(l))
+        .ok_or_else(|| eyre!("Could not find any changed rules"))?;
+    let references: Vec<_> = lines[first_reference..]
+        .iter()
+        .map_while(|l| RE_REFERENCE.captures(l))
+        .collect();
+    let descriptions: Vec<_> = lines[first_reference + references.len()..]
+        .iter()
+        .filter(|l| !l.is_empty())
+        .collect();
+    if references.len() != descriptions.len() {
+        return Err(eyre!(
+            "Found {} changed rules but {} descriptions",
+            references.len(),
+            descriptions.len()
+        ));
+    }
 
-    use insta::{assert_snapshot, assert_yaml_snapshot};
-    use maud::html;
-    use std::path::PathBuf;
-    use temp_testdir::TempDir;
+    references
+        .into_iter()
+        .zip(descriptions)
+        .map(|(reference, description)| {
+            Ok(RuleChange {
+                kind,
+                rule_ref: reference["rule_ref"].parse()?,
+                remark: reference.name("remark").map(|r| r.as_str().to_string()),
+                description: description.to_string(),
+            })
+        })
+        .collect()
+}
 
-    #[test]
-    fn test_parse() {
-        let temp = TempDir::default();
+enum BodyLine<'a> {
+    Text(&'a str),
+    Item {
+        depth: usize,
+        index: &'a str,
+        text: &'a str,
+    },
+    Exceptions {
+        depth: usize,
+        text: &'a str,
+    },
+}
 
-        let mut rules_path = PathBuf::from(temp.as_ref());
-        rules_path.push("rules.pdf");
+/// Add every rule and interpretation to the backlinks of the other rules it references
+fn add_backlinks(rules: &mut IndexMap<ArticleNr, Rule>) {
+    let mut backlinks = vec![];
+    for rule in rules.values() {
+        let source = Reference::Rule(rule.article_nr.into());
+        for reference in &rule.references {
+            backlinks.push((reference.article_nr(), source.clone()));
+        }
+        for interpretation in &rule.interpretations {
+            let source = Reference::Interpretation(interpretation.to_ref());
+            for reference in &interpretation.references {
+                backlinks.push((reference.article_nr(), source.clone()));
+            }
+        }
+    }
 
-        let rules_response = reqwest::blocking::get(RULE_BOOK_URL)
+    for (article_nr, source) in backlinks {
+        if source.article_nr() == article_nr {
+            continue;
+        }
+        if let Some(rule) = rules.get_mut(&article_nr)
+            && !rule.referenced_by.contains(&source)
+        {
+            rule.referenced_by.push(source);
+        }
+    }
+}
+
+/// Builds the block tree from the text whose listings are indented with tabs
+fn build_rule_body(text: &str) -> Vec<RuleBlock> {
+    let lines: Vec<_> = text
+        .lines()
+        .map(|l| {
+            let depth = l.len() - l.trim_start_matches('\t').len();
+            let line = l.trim();
+            if depth == 0 {
+                BodyLine::Text(line)
+            } else if line.starts_with("Ausnahmen") {
+                BodyLine::Exceptions { depth, text: line }
+            } else {
+                let (index, text) = line.split_once(' ').unwrap_or((line, ""));
+                BodyLine::Item {
+                    depth,
+                    index: index.trim_end_matches([')', '.']),
+                    text: text.trim(),
+                }
+            }
+        })
+        .collect();
+
+    parse_top_level_blocks(&lines, &mut 0, false)
+}
+
+fn parse_top_level_blocks(
+    lines: &[BodyLine],
+    pos: &mut usize,
+    inside_note: bool,
+) -> Vec<RuleBlock> {
+    static RE_NOTE_TITLE: Lazy<Regex> = Lazy::new(|| Regex::new(r"^Anmerkung \d+$").unwrap());
+
+    let mut blocks = vec![];
+    while let Some(line) = lines.get(*pos) {
+        match *line {
+            BodyLine::Text(text) if RE_NOTE_TITLE.is_match(text) => {
+                if inside_note {
+                    break;
+                }
+                *pos += 1;
+                blocks.push(RuleBlock::Note {
+                    title: text.to_string(),
+                    blocks: parse_top_level_blocks(lines, pos, true),
+                });
+            }
+            BodyLine::Text(text) => {
+                *pos += 1;
+                let (text, penalty) = split_penalty(text);
+                blocks.push(RuleBlock::Paragraph(text));
+                blocks.extend(penalty.map(RuleBlock::Penalty));
+            }
+            BodyLine::Item { depth, .. } => {
+                blocks.push(RuleBlock::List(parse_list(lines, pos, depth, &[])));
+            }
+            BodyLine::Exceptions { depth, text } => {
+                *pos += 1;
+                blocks.push(RuleBlock::Exceptions {
+                    text: text.to_string(),
+                    blocks: parse_nested_blocks(lines, pos, depth, &[], false),
+                });
+            }
+        }
+    }
+    blocks
+}
+
+/// Parses the blocks that are nested deeper than `depth`
+fn parse_nested_blocks(
+    lines: &[BodyLine],
+    pos: &mut usize,
+    depth: usize,
+    item_path: &[String],
+    is_item: bool,
+) -> Vec<RuleBlock> {
+    let mut blocks = vec![];
+    while let Some(line) = lines.get(*pos) {
+        match *line {
+            BodyLine::Item {
+                depth: item_depth, ..
+            } if item_depth > depth => {
+                blocks.push(RuleBlock::List(parse_list(
+                    lines, pos, item_depth, item_path,
+                )));
+            }
+            // Exceptions belong to the preceding item of the same listing
+            BodyLine::Exceptions {
+                depth: exceptions_depth,
+                text,
+            } if exceptions_depth > depth || (is_item && exceptions_depth == depth) => {
+                *pos += 1;
+                blocks.push(RuleBlock::Exceptions {
+                    text: text.to_string(),
+                    blocks: parse_nested_blocks(lines, pos, exceptions_depth, item_path, false),
+                });
+            }
+            _ => break,
+        }
+    }
+    blocks
+}
+
+fn parse_list(
+    lines: &[BodyLine],
+    pos: &mut usize,
+    depth: usize,
+    parent_path: &[String],
+) -> RuleList {
+    let mut items = vec![];
+    while let Some(&BodyLine::Item {
+        depth: item_depth,
+        index,
+        text,
+    }) = lines.get(*pos)
+        && item_depth == depth
+    {
+        *pos += 1;
+        let mut item_path = parent_path.to_vec();
+        item_path.push(index.to_string());
+        let (text, penalty) = split_penalty(text);
+        let mut blocks: Vec<_> = penalty.map(RuleBlock::Penalty).into_iter().collect();
+        blocks.extend(parse_nested_blocks(lines, pos, depth, &item_path, true));
+        items.push(ListItem {
+            item_path,
+            text,
+            blocks,
+        });
+    }
+    RuleList {
+        style: ListStyle::from_depth(depth),
+        items,
+    }
+}
+
+fn split_penalty(text: &str) -> (String, Option<String>) {
+    match text.find(" Strafe: ") {
+        Some(penalty_index) => (
+            text[..penalty_index].trim().to_string(),
+            Some(text[penalty_index + 1..].to_string()),
+        ),
+        None => (text.to_string(), None),
+    }
+}
+
+fn extract_penalties(text: &str, automatic_first_down_phrases: &[String]) -> Vec<Penalty> {
+    static RE_PENALTY: Lazy<Regex> =
+        Lazy::new(|| Regex::new(r"Strafe(?: \((?<applies_to>[^):]+)\)?)?: ").unwrap());
+
+    let mut penalties = vec![];
+    // A penalty ends with its line or with the next penalty
+    for line in text.lines() {
+        let captures: Vec<_> = RE_PENALTY.captures_iter(line).collect();
+        for (i, penalty) in captures.iter().enumerate() {
+            let penalty_end = captures
+                .get(i + 1)
+                .map_or(line.len(), |next| next.get(0).unwrap().start());
+            penalties.push(Penalty::new(
+                penalty.name("applies_to").map(|a| a.as_str().to_string()),
+                line[penalty.get(0).unwrap().end()..penalty_end]
+                    .trim()
+                    .to_string(),
+                automatic_first_down_phrases,
+            ));
+        }
+    }
+    penalties
+}
+
+static RE_DOT_LEADERS: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?:\s*\.){2,}\s*$|\s+\.\s*$").unwrap());
+
+#[derive(Default)]
+struct DescriptionColumn {
+    category: Option<String>,
+    spots: VecDeque<Vec<EnforcementSpot>>,
+    lines: Vec<String>,
+    entries: Vec<(String, Vec<EnforcementSpot>, String)>,
+}
+
+impl DescriptionColumn {
+    fn finish_entry(&mut self) {
+        if self.lines.is_empty() {
+            return;
+        }
+        let description = RE_DOT_LEADERS
+            .replace(&self.lines.join(" "), "")
+            .trim()
+            .to_string();
+        self.lines.clear();
+
+        // Skips the introduction before the first category
+        if let Some(category) = &self.category {
+            let spots = self.spots.pop_front().unwrap_or_default();
+            self.entries.push((category.clone(), spots, description));
+        }
+    }
+}
+
+struct ColumnGroup<'a> {
+    header: Option<&'a str>,
+    values: Vec<String>,
+}
+
+fn assemble_penalty_columns(
+    groups: &[ColumnGroup],
+) -> eyre::Result<(Vec<Vec<u8>>, Vec<ArticleNr>)> {
+    let mut signals: Vec<Vec<u8>> = vec![];
+    let mut references = vec![];
+    // Some tables have no signal column
+    let mut with_signals = true;
+
+    let mut i = 0;
+    while i < groups.len() {
+        let group = &groups[i];
+        match group.header {
+            Some("Signal") => with_signals = true,
+            Some("R") if i == 0 || groups[i - 1].header != Some("Signal") => with_signals = false,
+            _ => {}
+        }
+
+        if group.header == Some("R Ab Art") {
+            // Columns without space in between are output as rows
+            let values = group.values.join(" ");
+            let values: Vec<&str> = values.split_whitespace().collect();
+            for reference in values.chunks_exact(3) {
+                references.push(parse_article_nr(reference[0], reference[1], reference[2])?);
+            }
+            signals.resize(references.len(), vec![]);
+            with_signals = false;
+            i += 1;
+            continue;
+        }
+
+        if group.header == Some("Signal")
+            || (with_signals && group.header.is_none() && signals.len() == references.len())
+        {
+            signals.extend(group.values.iter().map(|v| {
+                v.split(',')
+                    .filter_map(|s| s.trim().parse().ok())
+                    .collect::<Vec<u8>>()
+            }));
+            i += 1;
+            continue;
+        }
+
+        let rules = &group.values;
+        let Some(next) = groups.get(i + 1) else {
+            break;
+        };
+        if next.values.len() == 2 * rules.len() {
+            // Sections and articles are printed alternating in a single column
+            for (rule, section_article) in rules.iter().zip(next.values.chunks_exact(2)) {
+                references.push(parse_article_nr(
+                    rule,
+                    &section_article[0],
+                    &section_article[1],
+                )?);
+            }
+            i += 2;
+        } else {
+            let Some(articles) = groups.get(i + 2) else {
+                break;
+            };
+            let sections = &next.values;
+            if sections.len() != rules.len() || articles.values.len() != rules.len() {
+                return Err(eyre!(
+                    "Columns of penalty summary have different lengths: {rules:?} {sections:?} {:?}",
+                    articles.values
+                ));
+            }
+            for ((rule, section), article) in rules.iter().zip(sections).zip(&articles.values) {
+                references.push(parse_article_nr(rule, section, article)?);
+            }
+            i += 3;
+        }
+
+        if !with_signals {
+            signals.resize(references.len(), vec![]);
+        }
+    }
+    signals.resize(references.len(), vec![]);
+
+    Ok((signals, references))
+}
+
+fn parse_article_nr(rule: &str, section: &str, article: &str) -> eyre::Result<ArticleNr> {
+    Ok(ArticleNr(rule.parse()?, section.parse()?, article.parse()?))
+}
+
+fn find_marker(text: &str, marker: &str) -> eyre::Result<usize> {
+    text.find(marker).ok_or_else(|| {
+        eyre!(
+            "Could not find '{}' inside the pdf text",
+            marker.escape_debug()
+        )
+    })
+}
+
+#[cfg(test)]
+pub(crate) const RULES_TEXT_FIXTURE: &str =
+    include_str!("../tests/fixtures/Football_Regelbuch_2026.txt");
+
+/// Rules of the text fixture that are parsed only once for the tests of all modules
+#[cfg(test)]
+pub(crate) fn parsed_rules_fixture() -> &'static IndexMap<ArticleNr, Rule> {
+    static RULES: Lazy<IndexMap<ArticleNr, Rule>> = Lazy::new(|| {
+        RulesParser::default()
+            .parse_text(RULES_TEXT_FIXTURE.to_string())
             .unwrap()
-            .error_for_status()
-            .unwrap();
+    });
+    &RULES
+}
 
-        std::fs::write(&rules_path, rules_response.bytes().unwrap()).unwrap();
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        reference::render_standard_with_links,
+        rule::{InterpretationRef, RuleRef},
+    };
 
-        let mut rules_text = RulesParser::extract_text_from_pdf(&rules_path).unwrap();
-        assert_snapshot!("text_from_pdf", rules_text);
+    use insta::{assert_snapshot, assert_yaml_snapshot};
+    use maud::html;
+
+    fn preprocessed_fixture() -> String {
+        RulesParser::default().preprocess_text(RULES_TEXT_FIXTURE.to_string())
+    }
 
-        rules_text = RulesParser::preprocess_text(rules_text);
-        assert_snapshot!("preprocessed_text", rules_text);
+    #[test]
+    fn test_preprocess_text() {
+        assert_snapshot!("preprocessed_text", preprocessed_fixture());
+    }
 
-        let rules = RulesParser::extract_rules(&rules_text).unwrap();
+    #[test]
+    fn test_extract_rules() {
+        let rules = RulesParser::default()
+            .extract_rules(&preprocessed_fixture())
+            .unwrap();
         for (article_nr, rule) in &rules {
             assert_yaml_snapshot!(format!("rule_{article_nr}"), rule);
         }
@@ -311,8 +1095,51 @@ mod tests {
         }) {
             assert_snapshot!(format!("rule_html_{article_nr}"), rule_html);
         }
+    }
+
+    #[test]
+    fn test_parse_with_profile() {
+        let contains_heading = |i: &RuleInterpretation| {
+            i.ruling
+                .lines()
+                .any(|l| l.starts_with("Abschnitt ") || l.starts_with("Artikel "))
+        };
+        let rules = parsed_rules_fixture();
+        assert!(
+            !rules
+                .values()
+                .flat_map(|r| &r.interpretations)
+                .any(contains_heading)
+        );
+
+        // A profile for another edition that rewords an interpretation and knows no headings
+        let mut profile = EditionProfile::from_toml(&format!(
+            "{}\n[[interpretation_replacements]]\nfrom = \"A.R. 9.1.10.II Als\"\nto = \"A.R. 9.1.10.II Sobald\"\n",
+            include_str!("../profiles/2026.toml")
+        ))
+        .unwrap();
+        profile.interpretation_headings.clear();
+        let rules = RulesParser::new(profile)
+            .parse_text(RULES_TEXT_FIXTURE.to_string())
+            .unwrap();
+        assert!(
+            rules[&ArticleNr(9, 1, 10)].interpretations[1]
+                .text
+                .starts_with("Sobald sich das Spielgeschehen")
+        );
+        assert!(
+            rules
+                .values()
+                .flat_map(|r| &r.interpretations)
+                .any(contains_heading)
+        );
+    }
 
-        let interpretations = RulesParser::extract_interpretations(&rules_text).unwrap();
+    #[test]
+    fn test_extract_interpretations() {
+        let interpretations = RulesParser::default()
+            .extract_interpretations(&preprocessed_fixture())
+            .unwrap();
         for interpretation in interpretations.values().flatten() {
             assert_yaml_snapshot!(
                 format!(
@@ -338,4 +1165,233 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_parse_text() {
+        let rules = RulesParser::default()
+            .parse_text(RULES_TEXT_FIXTURE.to_string())
+            .unwrap();
+        assert_eq!(rules.len(), 321);
+        assert_eq!(
+            rules
+                .values()
+                .map(|r| r.interpretations.len())
+                .sum::<usize>(),
+            550
+        );
+        assert_eq!(rules.values().filter(|r| r.changed_this_season).count(), 20);
+        assert_eq!(
+            rules
+                .values()
+                .flat_map(|r| &r.interpretations)
+                .filter(|i| i.changed_this_season)
+                .count(),
+            21
+        );
+    }
+
+    #[test]
+    fn test_resolve_rule_ref() {
+        let rules = RulesParser::default()
+            .extract_rules(&preprocessed_fixture())
+            .unwrap();
+        let rule_ref: RuleRef = "7.1.5.a.4".parse().unwrap();
+        assert_eq!(rule_ref.to_string(), "7.1.5.a.4");
+        assert_eq!(rule_ref.to_url(""), "/rule/7-1-5#a-4");
+        assert_eq!(
+            RuleRef::from_path_parameter(&rule_ref.to_path_parameter()).unwrap(),
+            rule_ref
+        );
+        assert!(
+            rule_ref
+                .resolve(&rules[&rule_ref.article_nr])
+                .unwrap()
+                .starts_with("Spieler, die sich innerhalb eines Meters")
+        );
+        assert!(
+            "7.1.5.c"
+                .parse::<RuleRef>()
+                .unwrap()
+                .resolve(&rules[&rule_ref.article_nr])
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_interpretation_ref() {
+        let interpretation_ref = InterpretationRef::from_path_parameters("10-2-3", "V").unwrap();
+        assert_eq!(interpretation_ref.to_string(), "A.R. 10.2.3.V");
+        assert_eq!(interpretation_ref.to_url(""), "/interpretation/10-2-3/V");
+        assert!(InterpretationRef::from_path_parameters("10-2-3", "5").is_err());
+    }
+
+    #[test]
+    fn test_find_references() {
+        let references = find_references(
+            "(Regel 2.21.2, 2.27.4 und 7.1.4.a) (A.R. 7.1.4.IV bis 7.1.4.VI) am 01.03.2026",
+        );
+        assert_eq!(
+            references.iter().map(|r| r.to_url("")).collect::<Vec<_>>(),
+            [
+                "/rule/2-21-2",
+                "/rule/2-27-4",
+                "/rule/7-1-4#a",
+                "/interpretation/7-1-4/IV",
+                "/interpretation/7-1-4/VI",
+            ]
+        );
+
+        // The standards refer to their own sections with numbers like "1.9.1"
+        assert_eq!(
+            render_standard_with_links("Abschnitt 1.9.1 und 1.15.2, 2.3.4 Meter").into_string(),
+            "Abschnitt 1.9.1 und 1.15.2, 2.3.4 Meter"
+        );
+        assert_eq!(
+            render_standard_with_links("Siehe 7.1.4 (Regel 7.3.2.f und 9.2.1, A.R. 7.3.4.I)")
+                .into_string(),
+            "Siehe 7.1.4 (Regel <a href=\"/rule/7-3-2#f\">7.3.2.f</a> und \
+             <a href=\"/rule/9-2-1\">9.2.1</a>, A.R. <a href=\"/interpretation/7-3-4/I\">7.3.4.I</a>)"
+        );
+
+        let rules = parsed_rules_fixture();
+        let backlinks = &rules[&ArticleNr(9, 1, 4)].referenced_by;
+        assert!(backlinks.contains(&Reference::Rule(ArticleNr(9, 1, 9).into())));
+        assert!(
+            rules
+                .values()
+                .flat_map(|r| &r.referenced_by)
+                .all(|r| rules.contains_key(&r.article_nr()))
+        );
+    }
+
+    #[test]
+    fn test_extract_changes() {
+        let changes = RulesParser::default()
+            .extract_changes(RULES_TEXT_FIXTURE)
+            .unwrap();
+        assert_eq!(changes.rules.len(), 22);
+        assert_eq!(changes.interpretations.len(), 21);
+        assert_yaml_snapshot!("season_changes", changes);
+    }
+
+    #[test]
+    fn test_automatic_first_down() {
+        let phrases = EditionProfile::default().automatic_first_down_phrases;
+        let penalties: Vec<_> = [
+            "15 Meter. Für Dead Ball Fouls 15 Meter vom Succeeding Spot. Automatisch ein First Down für Team B-Fouls, sofern der First Down nicht im Widerspruch zu anderen Regeln steht.",
+            "Dead Ball Foul, 15 Meter vom Succeeding Spot. Automatisch First Down für Team B Fouls, soweit das nicht in Konflikt zu anderen Regeln steht [S27].",
+            "15 Meter vom Basic Spot oder vom Succeeding Spot, automatisch First Down für Team B-Fouls, soweit der First Down nicht im Widerspruch zu anderen Regeln steht, und Disqualifikation [S7, S27, S38 und S47].",
+            "15 Meter, Previous Spot und automatisch First Down [S38].",
+            "Team A’s Ball am Spot of Foul und First Down, wenn sich das Foul weniger als 15 Meter jenseits des Previous Spots ereignet.",
+            // Mentions of a first down that is not granted by the penalty
+            "5 Meter vom Previous Spot. Die Line to Gain für den nächsten First Down bleibt bestehen.",
+            "15 Meter vom Previous Spot [S33].",
+        ]
+        .map(|text| Penalty::new(None, text.to_string(), &phrases))
+        .into_iter()
+        .collect();
+        assert_yaml_snapshot!("automatic_first_down", penalties);
+    }
+
+    #[test]
+    fn test_extract_penalty_summary() {
+        let penalty_summary = RulesParser::default()
+            .extract_penalty_summary(RULES_TEXT_FIXTURE)
+            .unwrap();
+        assert_eq!(penalty_summary.entries.len(), 164);
+        assert_yaml_snapshot!("penalty_summary", penalty_summary);
+    }
+
+    #[test]
+    fn test_extract_signals() {
+        let signals = RulesParser::default()
+            .extract_signals(RULES_TEXT_FIXTURE)
+            .unwrap();
+        assert_eq!(signals.len(), 47);
+        assert_yaml_snapshot!("signals", signals.values().collect::<Vec<_>>());
+
+        // Every signal needs a name, also when its caption cannot be checked
+        let mut profile = EditionProfile::default();
+        profile.transcribed_signals.retain(|s| s.nr != 1);
+        assert!(
+            RulesParser::new(profile)
+                .extract_signals(RULES_TEXT_FIXTURE)
+                .is_err()
+        );
+
+        // Captions that are not transcribed have to be found in the text
+        let mut profile = EditionProfile::default();
+        let signal = profile.signals.iter_mut().find(|s| s.nr == 23).unwrap();
+        signal.name = "Ball spielbereit".to_string();
+        assert!(
+            RulesParser::new(profile)
+                .extract_signals(RULES_TEXT_FIXTURE)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_extract_standards() {
+        let sections = RulesParser::default()
+            .extract_standards(RULES_TEXT_FIXTURE)
+            .unwrap();
+        assert_eq!(sections.len(), 15);
+        assert_yaml_snapshot!("standards", sections);
+
+        for (section_nr, section_html) in sections.iter().map(|s| {
+            (
+                s.section_nr,
+                tidier::format(html!((s)).into_string(), false, &Default::default()).unwrap(),
+            )
+        }) {
+            assert_snapshot!(format!("standard_html_{section_nr}"), section_html);
+        }
+    }
+
+    #[test]
+    fn test_extract_chapters() {
+        let chapters = RulesParser::default()
+            .extract_chapters(RULES_TEXT_FIXTURE)
+            .unwrap();
+        assert_eq!(chapters.len(), 11);
+        assert_eq!(
+            chapters[8].to_title(),
+            "Regel 9 Verhalten von Spielern und anderen, die den Regeln unterliegen"
+        );
+        assert_eq!(
+            chapters[8].sections[0].to_title(),
+            "Abschnitt 9.1 Persönliche Fouls"
+        );
+        assert_yaml_snapshot!("chapters", chapters);
+    }
+
+    // Run with `cargo test --features online-tests` to check the fixture against the current rule book
+    #[cfg(feature = "online-tests")]
+    #[test]
+    fn test_extract_text_from_pdf() {
+        use crate::{
+            RULE_BOOK_URL,
+            extractor::{ExtractionOptions, Pdftotext, TextExtractor},
+        };
+        use std::path::PathBuf;
+        use temp_testdir::TempDir;
+
+        let temp = TempDir::default();
+
+        let mut rules_path = PathBuf::from(temp.as_ref());
+        rules_path.push("rules.pdf");
+
+        let rules_response = reqwest::blocking::get(RULE_BOOK_URL)
+            .unwrap()
+            .error_for_status()
+            .unwrap();
+
+        std::fs::write(&rules_path, rules_response.bytes().unwrap()).unwrap();
+
+        let extractor = Pdftotext {
+            options: ExtractionOptions::default(),
+        };
+        let rules_text = extractor.extract_text(&rules_path).unwrap();
+        assert_eq!(rules_text.trim_end(), RULES_TEXT_FIXTURE.trim_end());
+    }
 }
@@ -0,0 +1,185 @@
+use eyre::eyre;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::Serialize;
+use std::{fmt::Display, str::FromStr};
+use utoipa::ToSchema;
+
+use crate::{rule::ArticleNr, signal::find_signal_references};
+
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq, Serialize, ToSchema)]
+pub enum EnforcementSpot {
+    Previous,
+    Succeeding,
+    SpotOfFoul,
+    Basic,
+}
+
+impl FromStr for EnforcementSpot {
+    type Err = eyre::Report;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "P" => Ok(Self::Previous),
+            "S" => Ok(Self::Succeeding),
+            "F" => Ok(Self::SpotOfFoul),
+            "B" => Ok(Self::Basic),
+            _ => Err(eyre!("Invalid enforcement spot: {s}")),
+        }
+    }
+}
+
+impl Display for EnforcementSpot {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EnforcementSpot::Previous => write!(f, "Previous Spot"),
+            EnforcementSpot::Succeeding => write!(f, "Succeeding Spot"),
+            EnforcementSpot::SpotOfFoul => write!(f, "Spot of Foul"),
+            EnforcementSpot::Basic => write!(f, "Basic Spot"),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq, Serialize, ToSchema)]
+pub enum FoulType {
+    LiveBall,
+    DeadBall,
+}
+
+/// Penalty stated after "Strafe:" inside the text of a rule.
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, ToSchema)]
+pub struct Penalty {
+    /// Listing items of the rule the penalty is restricted to, e.g. "a-c"
+    pub applies_to: Option<String>,
+    pub text: String,
+    pub foul_type: Option<FoulType>,
+    pub yardage: Option<u8>,
+    pub enforcement_spot: Option<EnforcementSpot>,
+    pub loss_of_down: bool,
+    pub automatic_first_down: bool,
+    pub disqualification: bool,
+    pub signals: Vec<u8>,
+}
+
+/// Whether the text contains one of the phrases regardless of case and line breaks
+fn contains_phrase(text: &str, phrases: &[String]) -> bool {
+    let normalize = |s: &str| {
+        s.split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ")
+            .to_lowercase()
+    };
+    let text = normalize(text);
+    phrases.iter().any(|p| text.contains(&normalize(p)))
+}
+
+impl Penalty {
+    pub fn new(
+        applies_to: Option<String>,
+        text: String,
+        automatic_first_down_phrases: &[String],
+    ) -> Self {
+        static RE_FOUL_TYPE: Lazy<Regex> =
+            Lazy::new(|| Regex::new(r"(?<foul_type>Live|Dead) Ball Foul\b").unwrap());
+        static RE_YARDAGE: Lazy<Regex> =
+            Lazy::new(|| Regex::new(r"(?<yardage>\d+)[- ]Meter(?:strafe)?\b").unwrap());
+        static RE_ENFORCEMENT_SPOT: Lazy<Regex> = Lazy::new(|| {
+            Regex::new(r"(?<spot>Previous|Succeeding|Basic) Spot|Spot of Foul|Punkt des Fouls")
+                .unwrap()
+        });
+        static RE_DISQUALIFICATION: Lazy<Regex> = Lazy::new(|| {
+            Regex::new(r"Disqualifikation|zu disqualifizieren|werden disqualifiziert").unwrap()
+        });
+
+        let foul_type = RE_FOUL_TYPE.captures(&text).map(|c| match &c["foul_type"] {
+            "Live" => FoulType::LiveBall,
+            _ => FoulType::DeadBall,
+        });
+        let yardage = RE_YARDAGE
+            .captures(&text)
+            .and_then(|c| c["yardage"].parse().ok());
+        let enforcement_spot =
+            RE_ENFORCEMENT_SPOT
+                .captures(&text)
+                .map(|c| match c.name("spot").map(|m| m.as_str()) {
+                    Some("Previous") => EnforcementSpot::Previous,
+                    Some("Succeeding") => EnforcementSpot::Succeeding,
+                    Some("Basic") => EnforcementSpot::Basic,
+                    _ => EnforcementSpot::SpotOfFoul,
+                });
+
+        Self {
+            foul_type,
+            yardage,
+            enforcement_spot,
+            loss_of_down: text.contains("Downverlust"),
+            automatic_first_down: contains_phrase(&text, automatic_first_down_phrases),
+            disqualification: RE_DISQUALIFICATION.is_match(&text),
+            signals: find_signal_references(&text),
+            applies_to,
+            text,
+        }
+    }
+}
+
+/// The "Zusammenfassung der Strafen" table of the rule book.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct PenaltySummary {
+    pub entries: Vec<PenaltySummaryEntry>,
+}
+
+impl PenaltySummary {
+    pub fn for_article(&self, article_nr: ArticleNr) -> impl Iterator<Item = &PenaltySummaryEntry> {
+        self.entries
+            .iter()
+            .filter(move |e| e.article_nr == article_nr)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PenaltySummaryEntry {
+    pub category: String,
+    pub description: String,
+    pub yardage: Option<u8>,
+    pub enforcement_spots: Vec<EnforcementSpot>,
+    pub loss_of_down: bool,
+    pub automatic_first_down: bool,
+    pub disqualification: bool,
+    pub signals: Vec<u8>,
+    pub article_nr: ArticleNr,
+}
+
+impl PenaltySummaryEntry {
+    pub fn new(
+        category: String,
+        description: String,
+        enforcement_spots: Vec<EnforcementSpot>,
+        signals: Vec<u8>,
+        article_nr: ArticleNr,
+        automatic_first_down_phrases: &[String],
+    ) -> Self {
+        static RE_YARDAGE: Lazy<Regex> =
+            Lazy::new(|| Regex::new(r"(?:Verlust von|auch) (?<yardage>\d+)[- ]Meter").unwrap());
+        static RE_DISQUALIFICATION: Lazy<Regex> =
+            Lazy::new(|| Regex::new(r"\b(?:DQ|Disqualifikation)\b").unwrap());
+
+        let yardage = RE_YARDAGE
+            .captures(&category)
+            .or_else(|| RE_YARDAGE.captures(&description))
+            .and_then(|c| c["yardage"].parse().ok());
+
+        Self {
+            yardage,
+            enforcement_spots,
+            loss_of_down: category == "Downverlust" || description.contains("auch Downverlust"),
+            automatic_first_down: contains_phrase(&category, automatic_first_down_phrases)
+                || contains_phrase(&description, automatic_first_down_phrases),
+            disqualification: RE_DISQUALIFICATION.is_match(&category)
+                || RE_DISQUALIFICATION.is_match(&description),
+            signals,
+            article_nr,
+            category,
+            description,
+        }
+    }
+}
@@ -0,0 +1,121 @@
+use eyre::Context;
+use serde::{Deserialize, Deserializer};
+use std::path::Path;
+
+use crate::{rule::ArticleNr, signal::Signal};
+
+const DEFAULT_PROFILE: &str = include_str!("../profiles/2026.toml");
+
+/// Edition specific knowledge that is needed to parse a rule book.
+#[derive(Debug, Clone, Deserialize)]
+pub struct EditionProfile {
+    pub markers: SectionMarkers,
+    #[serde(default)]
+    pub replacements: Vec<Replacement>,
+    /// First words of the headings of rules, sections and articles between the interpretations
+    #[serde(default)]
+    pub interpretation_headings: Vec<String>,
+    #[serde(default)]
+    pub interpretation_replacements: Vec<Replacement>,
+    /// Phrases of penalties that grant an automatic first down, compared case-insensitively
+    #[serde(default)]
+    pub automatic_first_down_phrases: Vec<String>,
+    #[serde(default)]
+    pub heading_replacements: Vec<Replacement>,
+    #[serde(default)]
+    pub rule_replacements: Vec<RuleReplacement>,
+    #[serde(default)]
+    pub rule_text_replacements: Vec<RuleReplacement>,
+    #[serde(default)]
+    pub nested_alpha_listings: Vec<RuleSelector>,
+    /// Captions of the signals that are checked against the extracted text
+    #[serde(default)]
+    pub signals: Vec<Signal>,
+    /// Captions of the signals that are missing in the extracted text
+    #[serde(default)]
+    pub transcribed_signals: Vec<Signal>,
+}
+
+impl EditionProfile {
+    pub fn load(profile_path: &Path) -> eyre::Result<Self> {
+        let profile = std::fs::read_to_string(profile_path)
+            .wrap_err_with(|| format!("Could not read profile {}", profile_path.display()))?;
+        Self::from_toml(&profile)
+    }
+
+    pub fn from_toml(profile: &str) -> eyre::Result<Self> {
+        toml::from_str(profile).wrap_err("Invalid edition profile")
+    }
+
+    pub fn has_nested_alpha_listing(&self, article_nr: ArticleNr, title: &str) -> bool {
+        self.nested_alpha_listings
+            .iter()
+            .any(|s| s.title == title && s.chapter.is_none_or(|c| c == article_nr.0))
+    }
+}
+
+impl Default for EditionProfile {
+    fn default() -> Self {
+        Self::from_toml(DEFAULT_PROFILE).expect("Default profile is invalid")
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct SectionMarkers {
+    pub rules_start: String,
+    pub rules_end: String,
+    pub last_rule_end: String,
+    pub interpretations_start: String,
+    pub interpretations_end: String,
+    pub penalty_summary_start: String,
+    pub penalty_summary_end: String,
+    pub signals_start: String,
+    pub signals_end: String,
+    pub standards_start: String,
+    pub standards_end: String,
+    pub rule_changes_start: String,
+    pub editorial_changes_start: String,
+    pub rule_changes_end: String,
+    pub new_interpretations_start: String,
+    pub changed_interpretations_start: String,
+    pub interpretation_changes_end: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Replacement {
+    pub from: String,
+    pub to: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct RuleReplacement {
+    #[serde(default, deserialize_with = "deserialize_optional_article_nr")]
+    pub article: Option<ArticleNr>,
+    pub from: String,
+    pub to: String,
+}
+
+impl RuleReplacement {
+    pub fn apply(&self, article_nr: ArticleNr, text: String) -> String {
+        if self.article.is_none_or(|a| a == article_nr) {
+            text.replace(&self.from, &self.to)
+        } else {
+            text
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct RuleSelector {
+    pub title: String,
+    pub chapter: Option<u8>,
+}
+
+fn deserialize_optional_article_nr<'de, D>(deserializer: D) -> Result<Option<ArticleNr>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Option::<String>::deserialize(deserializer)?
+        .map(|a| a.parse().map_err(serde::de::Error::custom))
+        .transpose()
+}
@@ -0,0 +1,206 @@
+use indexmap::IndexMap;
+use rand::seq::SliceRandom;
+use rand_pcg::Pcg64;
+use rand_seeder::Seeder;
+use roman_numerals::ToRoman;
+use serde::Deserialize;
+
+use crate::rule::{ArticleNr, Rule, RuleInterpretation};
+
+/// Number of rulings to choose from in a multiple choice question
+const OPTION_COUNT: usize = 4;
+const SCORE_COOKIE: &str = "quiz_score";
+const SCORE_MAX_AGE: u32 = 365 * 24 * 60 * 60;
+
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QuizMode {
+    /// Pick the ruling out of rulings of other interpretations
+    #[default]
+    Choice,
+    /// Reveal the ruling and tell whether it was known
+    SelfGraded,
+}
+
+impl QuizMode {
+    pub fn to_query_value(self) -> &'static str {
+        match self {
+            QuizMode::Choice => "choice",
+            QuizMode::SelfGraded => "self_graded",
+        }
+    }
+}
+
+/// Interpretation whose situation is asked with its ruling hidden among other rulings
+#[derive(Debug, Clone)]
+pub struct QuizQuestion<'a> {
+    pub interpretation: &'a RuleInterpretation,
+    pub options: Vec<&'a str>,
+    pub correct_option: usize,
+}
+
+impl<'a> QuizQuestion<'a> {
+    pub fn new(
+        rules: &'a IndexMap<ArticleNr, Rule>,
+        interpretation: &'a RuleInterpretation,
+    ) -> Self {
+        // The options only depend on the interpretation, so that a submitted answer can be checked
+        let mut rng: Pcg64 = Seeder::from(interpretation.to_ref()).into_rng();
+
+        let distractors = |same_chapter: bool| {
+            let mut rulings: Vec<&str> = rules
+                .values()
+                .filter(|r| !same_chapter || r.article_nr.0 == interpretation.article_nr.0)
+                .flat_map(|r| &r.interpretations)
+                .map(|i| i.ruling.as_str())
+                .filter(|r| *r != interpretation.ruling)
+                .collect();
+            rulings.sort_unstable();
+            rulings.dedup();
+            rulings
+        };
+        // Rulings of the same chapter are harder to tell apart
+        let mut options = distractors(true);
+        if options.len() < OPTION_COUNT - 1 {
+            options = distractors(false);
+        }
+        options.shuffle(&mut rng);
+        options.truncate(OPTION_COUNT - 1);
+        options.push(&interpretation.ruling);
+        options.shuffle(&mut rng);
+
+        let correct_option = options
+            .iter()
+            .position(|o| *o == interpretation.ruling)
+            .unwrap();
+        Self {
+            interpretation,
+            options,
+            correct_option,
+        }
+    }
+
+    pub fn to_url(&self, base_url: &str) -> String {
+        format!(
+            "{}/quiz/{}/{}",
+            base_url,
+            self.interpretation.article_nr.to_path_parameter(),
+            self.interpretation.index.to_roman()
+        )
+    }
+
+    pub fn is_correct(&self, choice: usize) -> bool {
+        choice == self.correct_option
+    }
+}
+
+/// Answers of a visitor, kept in a cookie instead of an account
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub struct QuizScore {
+    pub correct: u32,
+    pub total: u32,
+}
+
+impl QuizScore {
+    /// Score from the value of a Cookie header like "quiz_score=3-5; other=value"
+    pub fn from_cookies(cookies: &str) -> Self {
+        cookies
+            .split(';')
+            .filter_map(|c| c.trim().strip_prefix(SCORE_COOKIE)?.strip_prefix('='))
+            .find_map(|value| {
+                let (correct, total) = value.split_once('-')?;
+                let score = Self {
+                    correct: correct.parse().ok()?,
+                    total: total.parse().ok()?,
+                };
+                (score.correct <= score.total).then_some(score)
+            })
+            .unwrap_or_default()
+    }
+
+    /// Value of the Set-Cookie header that stores the score
+    pub fn to_cookie(self) -> String {
+        format!(
+            "{SCORE_COOKIE}={}-{}; Path=/quiz; Max-Age={SCORE_MAX_AGE}; SameSite=Lax",
+            self.correct, self.total
+        )
+    }
+
+    /// Value of the Set-Cookie header that removes the score
+    pub fn reset_cookie() -> String {
+        format!("{SCORE_COOKIE}=; Path=/quiz; Max-Age=0; SameSite=Lax")
+    }
+
+    pub fn add_answer(&mut self, correct: bool) {
+        // The cookie is under control of the visitor, so it may already be at the limit
+        self.total = self.total.saturating_add(1);
+        if correct {
+            self.correct = self.correct.saturating_add(1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parsed_rules_fixture;
+
+    #[test]
+    fn test_quiz_question() {
+        let rules = parsed_rules_fixture();
+        for interpretation in rules.values().flat_map(|r| &r.interpretations) {
+            let question = QuizQuestion::new(rules, interpretation);
+            assert_eq!(question.options.len(), OPTION_COUNT);
+            assert_eq!(
+                question.options[question.correct_option],
+                interpretation.ruling
+            );
+            assert_eq!(
+                question
+                    .options
+                    .iter()
+                    .filter(|o| **o == interpretation.ruling)
+                    .count(),
+                1
+            );
+            // The same interpretation always leads to the same options
+            assert_eq!(
+                question.options,
+                QuizQuestion::new(rules, interpretation).options
+            );
+        }
+    }
+
+    #[test]
+    fn test_quiz_score() {
+        assert_eq!(QuizScore::from_cookies(""), QuizScore::default());
+        assert_eq!(
+            QuizScore::from_cookies("theme=dark; quiz_score=3-5"),
+            QuizScore {
+                correct: 3,
+                total: 5
+            }
+        );
+        assert_eq!(
+            QuizScore::from_cookies("quiz_score=6-5"),
+            QuizScore::default()
+        );
+        assert_eq!(
+            QuizScore::from_cookies("quiz_score=abc"),
+            QuizScore::default()
+        );
+
+        let mut score = QuizScore::from_cookies("quiz_score=4294967295-4294967295");
+        score.add_answer(true);
+        assert_eq!(score.correct, u32::MAX);
+        assert_eq!(score.total, u32::MAX);
+
+        let mut score = QuizScore::default();
+        score.add_answer(true);
+        score.add_answer(false);
+        assert_eq!(
+            QuizScore::from_cookies(score.to_cookie().split(';').next().unwrap()),
+            score
+        );
+    }
+}
@@ -0,0 +1,145 @@
+use maud::{Markup, PreEscaped, Render, html};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use roman_numerals::FromRoman;
+use serde::Serialize;
+use std::ops::Range;
+use utoipa::ToSchema;
+
+use crate::{
+    rule::{ArticleNr, InterpretationRef, RuleRef},
+    signal::find_signal_spans,
+};
+
+static RE_REFERENCE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\b(?<article_nr>\d{1,2}\.\d{1,2}\.\d{1,2})\b(?:\.(?<index>[IVX]+)\b|(?<item_path>(?:\.(?:[a-z]|\d{1,2}\b))*))").unwrap()
+});
+
+/// Reference inside a text like "Regel 6.5.1.b" or "A.R. 10.2.3.V"
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, ToSchema)]
+pub enum Reference {
+    Rule(RuleRef),
+    Interpretation(InterpretationRef),
+}
+
+impl Reference {
+    pub fn article_nr(&self) -> ArticleNr {
+        match self {
+            Reference::Rule(rule_ref) => rule_ref.article_nr,
+            Reference::Interpretation(interpretation_ref) => interpretation_ref.article_nr,
+        }
+    }
+
+    pub fn to_title(&self) -> String {
+        match self {
+            Reference::Rule(rule_ref) => format!("Regel {rule_ref}"),
+            Reference::Interpretation(interpretation_ref) => interpretation_ref.to_string(),
+        }
+    }
+
+    pub fn to_url(&self, base_url: &str) -> String {
+        match self {
+            Reference::Rule(rule_ref) => rule_ref.to_url(base_url),
+            Reference::Interpretation(interpretation_ref) => interpretation_ref.to_url(base_url),
+        }
+    }
+}
+
+impl Render for Reference {
+    fn render(&self) -> Markup {
+        html! { a href=(self.to_url("")) { (self.to_title()) } }
+    }
+}
+
+/// Whether the number at the given position is preceded by "Regel" or "A.R." or continues a
+/// list of such references like "A.R. 7.3.4.I, 7.3.4.II und 7.3.4.IV"
+fn has_reference_context(text: &str, start: usize, previous_end: Option<usize>) -> bool {
+    let prefix = text[..start].trim_end();
+    ["Regel", "Regeln", "A.R."]
+        .iter()
+        .any(|context| prefix.ends_with(context))
+        || previous_end.is_some_and(|previous_end| {
+            matches!(
+                text[previous_end..start].trim(),
+                "," | "und" | "oder" | "bis" | ", und" | ", oder"
+            )
+        })
+}
+
+fn find_reference_spans(text: &str, require_context: bool) -> Vec<(Range<usize>, Reference)> {
+    let mut previous_end = None;
+    RE_REFERENCE
+        .captures_iter(text)
+        .filter(|c| {
+            let range = c.get(0).unwrap().range();
+            if require_context && !has_reference_context(text, range.start, previous_end) {
+                return false;
+            }
+            previous_end = Some(range.end);
+            true
+        })
+        .filter_map(|c| {
+            let article_nr = c["article_nr"].parse().ok()?;
+            let reference = match c.name("index") {
+                Some(index) => Reference::Interpretation(InterpretationRef {
+                    article_nr,
+                    index: u8::from_roman(index.as_str())?,
+                }),
+                None => Reference::Rule(RuleRef {
+                    article_nr,
+                    item_path: c["item_path"]
+                        .split('.')
+                        .filter(|p| !p.is_empty())
+                        .map(|p| p.to_string())
+                        .collect(),
+                }),
+            };
+            Some((c.get(0).unwrap().range(), reference))
+        })
+        .collect()
+}
+
+/// Distinct rules and interpretations referenced inside the text in order of appearance
+pub fn find_references(text: &str) -> Vec<Reference> {
+    let mut references = vec![];
+    for (_, reference) in find_reference_spans(text, false) {
+        if !references.contains(&reference) {
+            references.push(reference);
+        }
+    }
+    references
+}
+
+/// Render the text with links to all referenced signals, rules and interpretations
+pub fn render_with_links(text: &str) -> Markup {
+    render_links(text, false)
+}
+
+/// Render text of the standards with links, where only numbers after "Regel" or "A.R." are
+/// references since other numbers like "1.9.1" refer to the standards themselves
+pub fn render_standard_with_links(text: &str) -> Markup {
+    render_links(text, true)
+}
+
+fn render_links(text: &str, require_context: bool) -> Markup {
+    let mut links: Vec<_> = find_signal_spans(text)
+        .into_iter()
+        .map(|(range, signal_nr)| (range, format!("/signal/{signal_nr}")))
+        .chain(
+            find_reference_spans(text, require_context)
+                .into_iter()
+                .map(|(range, reference)| (range, reference.to_url(""))),
+        )
+        .collect();
+    links.sort_by_key(|(range, _)| range.start);
+
+    let mut markup = String::new();
+    let mut last_end = 0;
+    for (range, url) in links {
+        markup.push_str(&html! { (text[last_end..range.start]) }.into_string());
+        markup.push_str(&html! { a href=(url) { (text[range.clone()]) } }.into_string());
+        last_end = range.end;
+    }
+    markup.push_str(&html! { (text[last_end..]) }.into_string());
+    PreEscaped(markup)
+}
@@ -0,0 +1,887 @@
+use eyre::{Context, eyre};
+use indexmap::IndexMap;
+use jiff::{ToSpan, civil::Date};
+use maud::{Markup, Render, html};
+use once_cell::sync::Lazy;
+use rand_pcg::Pcg64;
+use rand_seeder::Seeder;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{HashSet, VecDeque},
+    fs::{self, File, OpenOptions},
+    io::Write,
+    path::{Path, PathBuf},
+};
+use tracing::{info, warn};
+
+use crate::{
+    rule::{ArticleNr, InterpretationRef, Rule, RuleInterpretation},
+    selection::{SelectionStrategy, Shuffle, Weighted, Weighting},
+};
+
+/// Name of the rotation of all rules that is shown on the start page
+pub const DEFAULT_ROTATION: &str = "default";
+/// Rotation behind the interpretation of the day
+pub const INTERPRETATION_ROTATION: &str = "interpretations";
+
+/// Days after the history that are planned in advance, this covers a calendar of a year ahead
+const PLANNED_DAYS: i32 = 366;
+
+static RE_ROTATION_NAME: Lazy<Regex> = Lazy::new(|| Regex::new(r"^[a-z0-9-]+$").unwrap());
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RotationPool {
+    #[default]
+    Rules,
+    Interpretations,
+    /// Rules and interpretations that were changed this season
+    Changes,
+}
+
+/// Named rotation with its own pool, order and pace
+#[derive(Debug, Clone, Deserialize)]
+pub struct RotationConfig {
+    pub name: String,
+    pub title: String,
+    #[serde(default)]
+    pub pool: RotationPool,
+    /// Chapters of the pool, all chapters if empty
+    #[serde(default)]
+    pub chapters: Vec<u8>,
+    pub start_date: Date,
+    /// Seed of the order, without a seed the order from before the schedule was persisted is continued
+    pub seed: Option<String>,
+    #[serde(default = "default_interval_days")]
+    pub interval_days: u16,
+    /// Items are shuffled uniformly without a weighting
+    pub weighting: Option<Weighting>,
+    pub schedule_path: Option<PathBuf>,
+    pub discord_channel_id: Option<u64>,
+    pub discord_post_hour: Option<u8>,
+}
+
+fn default_interval_days() -> u16 {
+    1
+}
+
+/// Configuration of the rotations besides the rule of the day
+#[derive(Debug, Default, Deserialize)]
+pub struct Rotations {
+    /// Weighting of the rule of the day
+    pub weighting: Option<Weighting>,
+    #[serde(default, rename = "rotation")]
+    pub rotations: Vec<RotationConfig>,
+}
+
+impl Rotations {
+    pub fn load(rotations_path: &Path) -> eyre::Result<Self> {
+        let rotations = fs::read_to_string(rotations_path)
+            .wrap_err_with(|| format!("Could not read rotations {}", rotations_path.display()))?;
+        Self::from_toml(&rotations)
+    }
+
+    pub fn from_toml(rotations: &str) -> eyre::Result<Self> {
+        let rotations: Self = toml::from_str(rotations).wrap_err("Invalid rotations")?;
+        let mut names = HashSet::from([DEFAULT_ROTATION, INTERPRETATION_ROTATION]);
+        for rotation in &rotations.rotations {
+            if !RE_ROTATION_NAME.is_match(&rotation.name) || !names.insert(&rotation.name) {
+                return Err(eyre!(
+                    "Invalid or duplicate rotation name: {}",
+                    rotation.name
+                ));
+            }
+            if rotation.interval_days == 0 {
+                return Err(eyre!("Interval of rotation {} is zero", rotation.name));
+            }
+            if rotation.discord_post_hour.is_some_and(|h| h > 23) {
+                return Err(eyre!(
+                    "Invalid Discord post hour of rotation {}",
+                    rotation.name
+                ));
+            }
+        }
+        Ok(rotations)
+    }
+}
+
+impl RotationConfig {
+    pub fn interpretation_of_the_day(start_date: Date) -> Self {
+        Self {
+            name: INTERPRETATION_ROTATION.to_string(),
+            title: "Auslegung des Tages".to_string(),
+            pool: RotationPool::Interpretations,
+            chapters: vec![],
+            start_date,
+            seed: Some(INTERPRETATION_ROTATION.to_string()),
+            interval_days: 1,
+            weighting: None,
+            schedule_path: None,
+            discord_channel_id: None,
+            discord_post_hour: None,
+        }
+    }
+
+    pub fn is_default(&self) -> bool {
+        self.name == DEFAULT_ROTATION
+    }
+
+    /// Histories without a configured path are kept next to the schedule of the rule of the day
+    pub fn get_schedule_path(&self, default_schedule_path: &Path) -> PathBuf {
+        self.schedule_path.clone().unwrap_or_else(|| {
+            default_schedule_path.with_file_name(format!("schedule_{}.jsonl", self.name))
+        })
+    }
+
+    pub fn to_url(&self, base_url: &str) -> String {
+        if self.is_default() {
+            base_url.to_string()
+        } else {
+            format!("{}/rotation/{}", base_url, self.name)
+        }
+    }
+
+    pub fn build_pool(&self, rules: &IndexMap<ArticleNr, Rule>) -> Vec<RotationItem> {
+        let rules = rules
+            .values()
+            .filter(|r| self.chapters.is_empty() || self.chapters.contains(&r.article_nr.0));
+        match self.pool {
+            RotationPool::Rules => rules.map(|r| RotationItem::Rule(r.article_nr)).collect(),
+            RotationPool::Interpretations => rules
+                .flat_map(|r| &r.interpretations)
+                .map(|i| RotationItem::Interpretation(i.to_ref()))
+                .collect(),
+            RotationPool::Changes => rules
+                .flat_map(|r| {
+                    r.changed_this_season
+                        .then_some(RotationItem::Rule(r.article_nr))
+                        .into_iter()
+                        .chain(
+                            r.interpretations
+                                .iter()
+                                .filter(|i| i.changed_this_season)
+                                .map(|i| RotationItem::Interpretation(i.to_ref())),
+                        )
+                })
+                .collect(),
+        }
+    }
+
+    pub fn build_strategy(
+        &self,
+        pool: &[RotationItem],
+        rules: &IndexMap<ArticleNr, Rule>,
+    ) -> Box<dyn SelectionStrategy> {
+        match &self.weighting {
+            Some(weighting) => Box::new(Weighted::new(pool, rules, weighting)),
+            None => Box::new(Shuffle),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RotationItem {
+    Rule(ArticleNr),
+    Interpretation(InterpretationRef),
+}
+
+impl RotationItem {
+    pub fn resolve(self, rules: &IndexMap<ArticleNr, Rule>) -> Option<RotationContent<'_>> {
+        match self {
+            RotationItem::Rule(article_nr) => rules.get(&article_nr).map(RotationContent::Rule),
+            RotationItem::Interpretation(interpretation_ref) => {
+                let rule = rules.get(&interpretation_ref.article_nr)?;
+                let interpretation = rule
+                    .interpretations
+                    .iter()
+                    .find(|i| i.index == interpretation_ref.index)?;
+                Some(RotationContent::Interpretation(rule, interpretation))
+            }
+        }
+    }
+}
+
+/// Rule or interpretation of a rotation
+#[derive(Debug, Copy, Clone)]
+pub enum RotationContent<'a> {
+    Rule(&'a Rule),
+    Interpretation(&'a Rule, &'a RuleInterpretation),
+}
+
+impl RotationContent<'_> {
+    pub fn to_title(&self) -> String {
+        match self {
+            RotationContent::Rule(rule) => rule.to_title(),
+            RotationContent::Interpretation(rule, interpretation) => {
+                format!("{} {}", interpretation.get_title(), rule.title)
+            }
+        }
+    }
+
+    pub fn to_url(&self, base_url: &str) -> String {
+        match self {
+            RotationContent::Rule(rule) => rule.to_url(base_url),
+            RotationContent::Interpretation(_, interpretation) => {
+                interpretation.to_ref().to_url(base_url)
+            }
+        }
+    }
+
+    pub fn to_description(&self) -> String {
+        match self {
+            RotationContent::Rule(rule) => rule.to_description(),
+            RotationContent::Interpretation(_, interpretation) => interpretation.to_description(),
+        }
+    }
+}
+
+impl Render for RotationContent<'_> {
+    fn render(&self) -> Markup {
+        match self {
+            RotationContent::Rule(rule) => html! { (rule) },
+            RotationContent::Interpretation(rule, interpretation) => html! {
+                h2.title ."is-4" { a href=(rule.to_url("")) { (rule.to_title()) } }
+                (interpretation)
+            },
+        }
+    }
+}
+
+/// Published item of a rotation that is shown from its date on until the next one
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScheduleEntry {
+    pub date: Date,
+    pub cycle: u32,
+    pub item: RotationItem,
+    /// Title of the item when it was published
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+}
+
+impl ScheduleEntry {
+    /// Content of the item unless it was removed or renumbered by a later edition of the rule book
+    pub fn resolve<'a>(&self, rules: &'a IndexMap<ArticleNr, Rule>) -> Option<RotationContent<'a>> {
+        let content = self.item.resolve(rules)?;
+        match &self.title {
+            Some(title) if *title != content.to_title() => None,
+            _ => Some(content),
+        }
+    }
+}
+
+/// Reads the entries of a history
+///
+/// A crash while appending can leave an incomplete last line behind. It is cut off so that the
+/// next entries are appended to a valid history again.
+fn read_history(path: &Path) -> eyre::Result<Vec<ScheduleEntry>> {
+    let history = fs::read_to_string(path)
+        .wrap_err_with(|| format!("Could not read schedule {}", path.display()))?;
+    let lines: Vec<_> = history.split_inclusive('\n').collect();
+    let mut entries = vec![];
+    let mut valid_len = 0;
+    for (i, line) in lines.iter().enumerate() {
+        if !line.trim().is_empty() {
+            match serde_json::from_str(line) {
+                Ok(entry) => entries.push(entry),
+                Err(e) if i == lines.len() - 1 => {
+                    warn!(
+                        "Dropping the incomplete last line of schedule {}: {e}",
+                        path.display()
+                    );
+                    OpenOptions::new()
+                        .write(true)
+                        .open(path)?
+                        .set_len(valid_len as u64)?;
+                    break;
+                }
+                Err(e) => {
+                    return Err(e).wrap_err_with(|| {
+                        format!("Invalid line {} of schedule {}", i + 1, path.display())
+                    });
+                }
+            }
+        }
+        valid_len += line.len();
+    }
+    Ok(entries)
+}
+
+/// Schedule of a rotation that is stored as an append-only history
+///
+/// Every cycle shows the items of the pool in the order of the selection strategy. Changes of the
+/// pool only affect the days that are not published yet, so the history never changes
+/// retroactively.
+#[derive(Debug)]
+pub struct Schedule {
+    start_date: Date,
+    seed: Option<String>,
+    interval_days: u16,
+    pool: Vec<RotationItem>,
+    strategy: Box<dyn SelectionStrategy>,
+    entries: Vec<ScheduleEntry>,
+    /// Entries after the history, planned once instead of on every lookup
+    planned: Vec<ScheduleEntry>,
+    history_path: Option<PathBuf>,
+}
+
+impl Schedule {
+    /// Loads the history from the JSON lines file at the path if there is one
+    pub fn load(
+        history_path: Option<&Path>,
+        rotation: &RotationConfig,
+        pool: Vec<RotationItem>,
+        strategy: Box<dyn SelectionStrategy>,
+    ) -> eyre::Result<Self> {
+        if pool.is_empty() {
+            return Err(eyre!("Pool of rotation {} is empty", rotation.name));
+        }
+        let entries = match history_path {
+            Some(path) if path.exists() => read_history(path)?,
+            _ => vec![],
+        };
+        info!(
+            "Loaded {} entries of the schedule of rotation {}",
+            entries.len(),
+            rotation.name
+        );
+        if entries.windows(2).any(|e| e[0].date >= e[1].date) {
+            return Err(eyre!("Dates of the schedule history are not ascending"));
+        }
+        let mut schedule = Self {
+            start_date: rotation.start_date,
+            seed: rotation.seed.clone(),
+            interval_days: rotation.interval_days,
+            pool,
+            strategy,
+            entries,
+            planned: vec![],
+            history_path: history_path.map(Path::to_path_buf),
+        };
+        schedule.plan_ahead();
+        Ok(schedule)
+    }
+
+    /// Loads the schedule with the pool and the selection strategy of the rotation
+    pub fn load_for_rules(
+        history_path: Option<&Path>,
+        rotation: &RotationConfig,
+        rules: &IndexMap<ArticleNr, Rule>,
+    ) -> eyre::Result<Self> {
+        let pool = rotation.build_pool(rules);
+        let strategy = rotation.build_strategy(&pool, rules);
+        Self::load(history_path, rotation, pool, strategy)
+    }
+
+    /// All published entries in ascending order
+    pub fn entries(&self) -> &[ScheduleEntry] {
+        &self.entries
+    }
+
+    /// Publishes and stores all entries until the date together with the titles of their items
+    pub fn publish_until(
+        &mut self,
+        date: Date,
+        rules: &IndexMap<ArticleNr, Rule>,
+    ) -> eyre::Result<()> {
+        let mut new_entries = self.plan(date);
+        if new_entries.is_empty() {
+            return Ok(());
+        }
+        for entry in &mut new_entries {
+            entry.title = entry.item.resolve(rules).map(|c| c.to_title());
+        }
+        if let Some(path) = &self.history_path {
+            let mut file: File = OpenOptions::new().create(true).append(true).open(path)?;
+            for entry in &new_entries {
+                writeln!(file, "{}", serde_json::to_string(entry)?)?;
+            }
+        }
+        self.entries.extend(new_entries);
+        self.plan_ahead();
+        Ok(())
+    }
+
+    /// Entry that is shown at the date, entries after the history are planned without publishing them
+    ///
+    /// Dates further ahead than the planned days have no entry.
+    pub fn get_entry(&self, date: Date) -> Option<ScheduleEntry> {
+        if date < self.start_date {
+            return None;
+        }
+        let published = self.entries.partition_point(|e| e.date <= date);
+        if published < self.entries.len()
+            || self
+                .entries
+                .last()
+                .is_some_and(|last| date < self.next_date(last.date))
+        {
+            return published.checked_sub(1).map(|i| self.entries[i].clone());
+        }
+        let planned = self.planned.partition_point(|e| e.date <= date);
+        if planned == self.planned.len()
+            && self
+                .planned
+                .last()
+                .is_none_or(|last| date >= self.next_date(last.date))
+        {
+            return None;
+        }
+        planned.checked_sub(1).map(|i| self.planned[i].clone())
+    }
+
+    /// Whether a new entry is published at the date instead of showing the last one again
+    pub fn is_new_on(&self, date: Date) -> bool {
+        self.entries.last().is_some_and(|last| last.date == date)
+    }
+
+    pub fn get_item(&self, date: Date) -> Option<RotationItem> {
+        self.get_entry(date).map(|e| e.item)
+    }
+
+    fn next_date(&self, date: Date) -> Date {
+        date.checked_add(i32::from(self.interval_days).days())
+            .expect("Date is not the last day")
+    }
+
+    fn plan_ahead(&mut self) {
+        let last_date = self.entries.last().map_or(self.start_date, |e| e.date);
+        let until = last_date
+            .checked_add(PLANNED_DAYS.days())
+            .unwrap_or(Date::MAX);
+        self.planned = self.plan(until);
+    }
+
+    /// Entries after the history until the date continuing its current cycle
+    fn plan(&self, until: Date) -> Vec<ScheduleEntry> {
+        let Some(last) = self.entries.last() else {
+            return match self.seed {
+                None => self.legacy_entries(until),
+                Some(_) => self.plan_cycles(self.start_date, 0, &[], until),
+            };
+        };
+        let used: Vec<_> = self
+            .entries
+            .iter()
+            .rev()
+            .take_while(|e| e.cycle == last.cycle)
+            .map(|e| e.item)
+            .collect();
+        self.plan_cycles(self.next_date(last.date), last.cycle, &used, until)
+    }
+
+    fn plan_cycles(
+        &self,
+        mut date: Date,
+        mut cycle: u32,
+        used: &[RotationItem],
+        until: Date,
+    ) -> Vec<ScheduleEntry> {
+        let mut remaining = VecDeque::from(self.cycle_order(cycle));
+        for item in used {
+            if let Some(index) = remaining.iter().position(|i| i == item) {
+                remaining.remove(index);
+            }
+        }
+
+        let mut entries = vec![];
+        while date <= until {
+            if remaining.is_empty() {
+                cycle += 1;
+                remaining = VecDeque::from(self.cycle_order(cycle));
+            }
+            let Some(item) = remaining.pop_front() else {
+                break;
+            };
+            entries.push(ScheduleEntry {
+                date,
+                cycle,
+                item,
+                title: None,
+            });
+            date = self.next_date(date);
+        }
+        entries
+    }
+
+    fn cycle_order(&self, cycle: u32) -> Vec<RotationItem> {
+        let mut rng: Pcg64 = match &self.seed {
+            Some(seed) => Seeder::from((&self.start_date, seed, cycle)).into_rng(),
+            None => Seeder::from((&self.start_date, cycle)).into_rng(),
+        };
+        self.strategy.cycle_order(&self.pool, &mut rng)
+    }
+
+    /// Entries since the start date as they were computed from a single shuffle of the pool
+    fn legacy_entries(&self, until: Date) -> Vec<ScheduleEntry> {
+        let mut rng: Pcg64 = Seeder::from(&self.start_date).into_rng();
+        let order = Shuffle.cycle_order(&self.pool, &mut rng);
+
+        self.start_date
+            .series(i32::from(self.interval_days).days())
+            .take_while(|date| *date <= until)
+            .enumerate()
+            .map(|(i, date)| ScheduleEntry {
+                date,
+                cycle: (i / order.len()) as u32,
+                item: order[i % order.len()],
+                title: None,
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parser::parsed_rules_fixture, selection::ChapterWeight};
+    use temp_testdir::TempDir;
+
+    fn pool(count: u8) -> Vec<RotationItem> {
+        (1..=count)
+            .map(|i| RotationItem::Rule(ArticleNr(9, 1, i)))
+            .collect()
+    }
+
+    fn rotation(seed: Option<&str>, interval_days: u16) -> RotationConfig {
+        RotationConfig {
+            name: "test".to_string(),
+            title: "Test".to_string(),
+            pool: RotationPool::Rules,
+            chapters: vec![],
+            start_date: Date::constant(2026, 1, 1),
+            seed: seed.map(str::to_string),
+            interval_days,
+            weighting: None,
+            schedule_path: None,
+            discord_channel_id: None,
+            discord_post_hour: None,
+        }
+    }
+
+    #[test]
+    fn test_legacy_schedule() {
+        let rotation = rotation(None, 1);
+        let mut schedule = Schedule::load(None, &rotation, pool(5), Box::new(Shuffle)).unwrap();
+        let planned: Vec<_> = rotation
+            .start_date
+            .series(1.day())
+            .take(12)
+            .map(|date| schedule.get_item(date).unwrap())
+            .collect();
+        schedule
+            .publish_until(Date::constant(2026, 1, 12), &IndexMap::new())
+            .unwrap();
+
+        let entries = schedule.entries();
+        assert_eq!(entries.len(), 12);
+        assert_eq!(entries.iter().map(|e| e.item).collect::<Vec<_>>(), planned);
+        assert_eq!(entries[4].cycle, 0);
+        assert_eq!(entries[5].cycle, 1);
+        // Before the schedule was persisted all cycles had the same order
+        assert_eq!(entries[0].item, entries[5].item);
+        assert_eq!(schedule.get_item(Date::constant(2025, 12, 31)), None);
+    }
+
+    #[test]
+    fn test_schedule_survives_pool_changes() {
+        let temp = TempDir::default();
+        let history_path = temp.as_ref().join("schedule_pool_changes.jsonl");
+        let _ = fs::remove_file(&history_path);
+        let rotation = rotation(None, 1);
+
+        let mut schedule =
+            Schedule::load(Some(&history_path), &rotation, pool(5), Box::new(Shuffle)).unwrap();
+        schedule
+            .publish_until(Date::constant(2026, 1, 7), &IndexMap::new())
+            .unwrap();
+        let published = schedule.entries().to_vec();
+        let planned = schedule.get_item(Date::constant(2026, 1, 8)).unwrap();
+
+        // Excluding a rule does not change the published days
+        let mut reduced_pool = pool(5);
+        reduced_pool.retain(|i| *i != planned);
+        let mut schedule = Schedule::load(
+            Some(&history_path),
+            &rotation,
+            reduced_pool,
+            Box::new(Shuffle),
+        )
+        .unwrap();
+        assert_eq!(schedule.entries(), published);
+        assert_ne!(schedule.get_item(Date::constant(2026, 1, 8)), Some(planned));
+
+        // The current cycle is finished before a new one starts
+        schedule
+            .publish_until(Date::constant(2026, 1, 9), &IndexMap::new())
+            .unwrap();
+        let cycle: Vec<_> = schedule.entries()[5..]
+            .iter()
+            .filter(|e| e.cycle == 1)
+            .map(|e| e.item)
+            .collect();
+        assert_eq!(cycle.len(), 4);
+        assert_eq!(cycle.iter().collect::<HashSet<_>>().len(), 4);
+        assert!(!cycle.contains(&planned));
+
+        let reloaded =
+            Schedule::load(Some(&history_path), &rotation, pool(5), Box::new(Shuffle)).unwrap();
+        assert_eq!(reloaded.entries(), schedule.entries());
+    }
+
+    #[test]
+    fn test_truncated_history() {
+        let temp = TempDir::default();
+        let history_path = temp.as_ref().join("schedule_truncated.jsonl");
+        let _ = fs::remove_file(&history_path);
+        let rotation = rotation(None, 1);
+
+        let mut schedule =
+            Schedule::load(Some(&history_path), &rotation, pool(5), Box::new(Shuffle)).unwrap();
+        schedule
+            .publish_until(Date::constant(2026, 1, 3), &IndexMap::new())
+            .unwrap();
+        let published = schedule.entries().to_vec();
+
+        // A crash while appending the entry of the next day
+        let mut file = OpenOptions::new().append(true).open(&history_path).unwrap();
+        write!(file, r#"{{"date":"2026-01-04","cyc"#).unwrap();
+        drop(file);
+
+        let mut schedule =
+            Schedule::load(Some(&history_path), &rotation, pool(5), Box::new(Shuffle)).unwrap();
+        assert_eq!(schedule.entries(), published);
+        schedule
+            .publish_until(Date::constant(2026, 1, 4), &IndexMap::new())
+            .unwrap();
+        let reloaded =
+            Schedule::load(Some(&history_path), &rotation, pool(5), Box::new(Shuffle)).unwrap();
+        assert_eq!(reloaded.entries(), schedule.entries());
+
+        // Invalid lines before the last one are not dropped silently
+        let history = fs::read_to_string(&history_path).unwrap();
+        fs::write(&history_path, format!("{{}}\n{history}")).unwrap();
+        assert!(
+            Schedule::load(Some(&history_path), &rotation, pool(5), Box::new(Shuffle)).is_err()
+        );
+    }
+
+    #[test]
+    fn test_slow_rotation() {
+        let youth = rotation(Some("youth"), 3);
+        let mut schedule = Schedule::load(None, &youth, pool(5), Box::new(Shuffle)).unwrap();
+        schedule
+            .publish_until(Date::constant(2026, 1, 20), &IndexMap::new())
+            .unwrap();
+
+        let entries = schedule.entries();
+        assert_eq!(entries.len(), 7);
+        assert_eq!(entries[1].date, Date::constant(2026, 1, 4));
+        assert_eq!(entries[5].cycle, 1);
+        assert_eq!(
+            schedule.get_item(Date::constant(2026, 1, 6)),
+            Some(entries[1].item)
+        );
+        // The last entry is shown until the next one is due
+        assert_eq!(
+            schedule.get_item(Date::constant(2026, 1, 21)),
+            Some(entries[6].item)
+        );
+        assert_eq!(
+            schedule.get_item(Date::constant(2026, 1, 22)),
+            Schedule::load(None, &youth, pool(5), Box::new(Shuffle))
+                .unwrap()
+                .get_item(Date::constant(2026, 1, 22))
+        );
+
+        let seniors = Schedule::load(
+            None,
+            &rotation(Some("seniors"), 3),
+            pool(5),
+            Box::new(Shuffle),
+        )
+        .unwrap();
+        assert_ne!(
+            entries.iter().map(|e| e.item).collect::<Vec<_>>(),
+            seniors
+                .plan(Date::constant(2026, 1, 20))
+                .iter()
+                .map(|e| e.item)
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_weekly_rotation() {
+        let rotation = rotation(Some("fouls"), 7);
+        let mut schedule = Schedule::load(None, &rotation, pool(5), Box::new(Shuffle)).unwrap();
+        let new_days: Vec<_> = rotation
+            .start_date
+            .series(1.day())
+            .take(15)
+            .filter(|date| {
+                schedule.publish_until(*date, &IndexMap::new()).unwrap();
+                schedule.is_new_on(*date)
+            })
+            .collect();
+        // Only the days with a new item are posted, the days in between show the last one again
+        assert_eq!(
+            new_days,
+            [
+                Date::constant(2026, 1, 1),
+                Date::constant(2026, 1, 8),
+                Date::constant(2026, 1, 15)
+            ]
+        );
+        assert_eq!(
+            schedule.get_item(Date::constant(2026, 1, 14)),
+            Some(schedule.entries()[1].item)
+        );
+    }
+
+    #[test]
+    fn test_far_future() {
+        let rotation = rotation(Some("future"), 1);
+        let mut schedule = Schedule::load(None, &rotation, pool(5), Box::new(Shuffle)).unwrap();
+        let horizon = rotation
+            .start_date
+            .checked_add(PLANNED_DAYS.days())
+            .unwrap();
+        assert!(schedule.get_item(horizon).is_some());
+        assert_eq!(schedule.get_item(horizon.tomorrow().unwrap()), None);
+        assert_eq!(schedule.get_item(Date::constant(9999, 12, 31)), None);
+
+        // The planned days move on with the history
+        let today = Date::constant(2026, 3, 1);
+        let planned = schedule.get_item(today);
+        schedule.publish_until(today, &IndexMap::new()).unwrap();
+        assert_eq!(schedule.get_item(today), planned);
+        assert!(
+            schedule
+                .get_item(today.checked_add(PLANNED_DAYS.days()).unwrap())
+                .is_some()
+        );
+    }
+
+    #[test]
+    fn test_rotation_config() {
+        let config = Rotations::from_toml(include_str!("../rotations.example.toml")).unwrap();
+        assert!(config.weighting.is_some());
+        let rotations = config.rotations;
+        assert!(rotations.iter().all(|r| !r.is_default()));
+        assert_eq!(
+            rotations[0].to_url("https://ruleoftheday.de"),
+            format!("https://ruleoftheday.de/rotation/{}", rotations[0].name)
+        );
+        assert!(
+            Rotations::from_toml(
+                "[[rotation]]\nname = \"default\"\ntitle = \"Test\"\nstart_date = \"2026-01-01\""
+            )
+            .is_err()
+        );
+        assert!(
+            Rotations::from_toml(
+                "[[rotation]]\nname = \"interpretations\"\ntitle = \"Test\"\nstart_date = \"2026-01-01\""
+            )
+            .is_err()
+        );
+
+        let rules = parsed_rules_fixture();
+        let interpretations = RotationConfig::interpretation_of_the_day(Date::constant(2026, 1, 1));
+        for rotation in rotations.iter().chain([&interpretations]) {
+            let pool = rotation.build_pool(rules);
+            assert!(!pool.is_empty());
+            assert!(pool.iter().all(|i| i.resolve(rules).is_some()));
+        }
+        let mut changes = rotation(None, 1);
+        changes.pool = RotationPool::Changes;
+        let pool = changes.build_pool(rules);
+        assert!(pool.iter().any(|i| matches!(i, RotationItem::Rule(_))));
+        assert!(
+            pool.iter()
+                .any(|i| matches!(i, RotationItem::Interpretation(_)))
+        );
+    }
+
+    #[test]
+    fn test_weighted_schedule() {
+        let rules = parsed_rules_fixture();
+        let mut rotation = rotation(Some("weighted"), 1);
+        rotation.chapters = vec![1, 9];
+        rotation.weighting = Some(Weighting {
+            chapters: vec![ChapterWeight {
+                chapter: 9,
+                weight: 3,
+            }],
+            ..Default::default()
+        });
+        let pool = rotation.build_pool(rules);
+        let load = || Schedule::load_for_rules(None, &rotation, rules).unwrap();
+
+        let fouls = pool
+            .iter()
+            .filter(|i| matches!(i, RotationItem::Rule(a) if a.0 == 9))
+            .count();
+        let cycle_length = pool.len() + 2 * fouls;
+        let mut schedule = load();
+        let last_date = rotation
+            .start_date
+            .checked_add((cycle_length as i32 - 1).days())
+            .unwrap();
+        schedule.publish_until(last_date, rules).unwrap();
+
+        let entries = schedule.entries();
+        assert_eq!(entries.len(), cycle_length);
+        assert!(entries.iter().all(|e| e.cycle == 0));
+        for item in &pool {
+            let expected = match item {
+                RotationItem::Rule(a) if a.0 == 9 => 3,
+                _ => 1,
+            };
+            assert_eq!(entries.iter().filter(|e| e.item == *item).count(), expected);
+        }
+        // All users see the same rule at the same date
+        let tomorrow = last_date.tomorrow().unwrap();
+        assert_eq!(schedule.get_item(tomorrow), load().get_item(tomorrow));
+        assert_eq!(schedule.get_entry(tomorrow).unwrap().cycle, 1);
+    }
+
+    #[test]
+    fn test_schedule_survives_new_edition() {
+        let rules = parsed_rules_fixture();
+        let temp = TempDir::default();
+        let history_path = temp.as_ref().join("schedule_new_edition.jsonl");
+        let _ = fs::remove_file(&history_path);
+        let rotation = rotation(Some("edition"), 1);
+        let date = Date::constant(2026, 1, 3);
+
+        let mut schedule = Schedule::load_for_rules(Some(&history_path), &rotation, rules).unwrap();
+        schedule.publish_until(date, rules).unwrap();
+        let entry = schedule.get_entry(date).unwrap();
+        let RotationItem::Rule(article_nr) = entry.item else {
+            panic!("Rotation of rules contains {:?}", entry.item);
+        };
+        assert_eq!(entry.title, Some(entry.resolve(rules).unwrap().to_title()));
+
+        // A rule with the same number but another title is not shown for the old day
+        let mut new_edition = rules.clone();
+        new_edition.get_mut(&article_nr).unwrap().title = "Neuer Titel".to_string();
+        let schedule =
+            Schedule::load_for_rules(Some(&history_path), &rotation, &new_edition).unwrap();
+        let entry = schedule.get_entry(date).unwrap();
+        assert!(entry.resolve(&new_edition).is_none());
+        assert!(entry.title.is_some());
+
+        // Removed rules are not available either
+        new_edition.shift_remove(&article_nr);
+        let schedule =
+            Schedule::load_for_rules(Some(&history_path), &rotation, &new_edition).unwrap();
+        assert!(
+            schedule
+                .get_entry(date)
+                .unwrap()
+                .resolve(&new_edition)
+                .is_none()
+        );
+    }
+}
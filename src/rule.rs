@@ -1,72 +1,37 @@
-use crate::RULE_BOOK_URL;
+use crate::{
+    RULE_BOOK_URL,
+    body::{ListItem, RuleBlock},
+    penalty::Penalty,
+    reference::{Reference, render_with_links},
+    signal::find_signal_references,
+};
 use eyre::eyre;
-use maud::{PreEscaped, Render, html};
-use roman_numerals::ToRoman;
-use serde::Serialize;
+use indexmap::IndexMap;
+use maud::{Render, html};
+use roman_numerals::{FromRoman, ToRoman};
+use serde::{Deserialize, Serialize};
 use std::{fmt::Display, str::FromStr};
+use utoipa::ToSchema;
 
-#[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize)]
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, ToSchema)]
 pub struct Rule {
     pub article_nr: ArticleNr,
     pub title: String,
     pub text: String,
+    pub body: Vec<RuleBlock>,
+    pub penalties: Vec<Penalty>,
     pub interpretations: Vec<RuleInterpretation>,
+    pub references: Vec<Reference>,
+    pub referenced_by: Vec<Reference>,
+    pub changed_this_season: bool,
 }
 
 impl Rule {
-    #[allow(clippy::comparison_chain)]
     pub fn render_text(&self) -> maud::Markup {
-        let mut current_indent = 0u8;
-        let processed_lines = self.text.lines().map(|l| {
-            let (new_indent, list_type) = if l.starts_with("\t\t\t") {
-                (3, "a")
-            } else if l.starts_with("\t\t") {
-                (2, "1")
-            } else if l.starts_with('\t') {
-                (1, "a")
-            } else {
-                (0, "")
-            };
-            let penalty_index = l.find(" Strafe: ");
-
-            let markup = html! {
-                @if new_indent > current_indent {
-                    (PreEscaped(format!("<ol type={list_type}>")))
-                } @else if new_indent < current_indent {
-                    @for _ in 0..(current_indent - new_indent){
-                        (PreEscaped("</ol>"))
-                    }
-                }
-                @if new_indent != 0 && !l.starts_with("\tAusnahmen") {
-                    li { ({
-                        let space_index = l.find(' ').expect("Missing space after enumeration index.");
-                        if let Some(penalty_index) = penalty_index {
-                            html! {
-                                p { (&l[space_index..penalty_index + 1]) }
-                                p { strong { (&l[penalty_index + 1..]) } }
-                            }
-                        } else {
-                            html! {
-                                ( &l[space_index..] )
-                            }
-                        }
-                    }) }
-                } @else if let Some(penalty_index) = penalty_index {
-                    p { (l[..penalty_index + 1]) }
-                    p { strong { (l[penalty_index + 1..]) } }
-                } @else if l.starts_with("\tAusnahmen") {
-                    p { strong { (l) } }
-                } @else {
-                    p { (l) }
-                }
-            };
-            current_indent = new_indent;
-            markup
-        });
         html! {
             .content {
-                @for line in processed_lines {
-                    (line)
+                @for block in &self.body {
+                    (block)
                 }
             }
         }
@@ -92,6 +57,15 @@ impl Rule {
     pub fn to_url(&self, base_url: &str) -> String {
         format!("{}/rule/{}", base_url, self.article_nr.to_path_parameter())
     }
+
+    pub fn uses_signal(&self, signal_nr: u8) -> bool {
+        find_signal_references(&self.text).contains(&signal_nr)
+    }
+
+    /// Listing item with the given path like ["a", "4"]
+    pub fn find_item(&self, item_path: &[String]) -> Option<&ListItem> {
+        self.body.iter().find_map(|b| b.find_item(item_path))
+    }
 }
 
 impl Render for Rule {
@@ -100,6 +74,9 @@ impl Render for Rule {
             article.message ."is-size-4" {
                 div.message-header {
                     p { (self.article_nr) " " (self.title) }
+                    @if self.changed_this_season {
+                        span.tag .is-warning { "Geändert" }
+                    }
                 }
                 div.message-body {
                     (self.render_text())
@@ -110,6 +87,16 @@ impl Render for Rule {
                             "Offizielles Regelwerk"
                         }
                     }
+                    @if !self.referenced_by.is_empty() {
+                        div.block {
+                            p { b { "Referenziert von:" } }
+                            div.tags {
+                                @for reference in &self.referenced_by {
+                                    span.tag .is-medium { (reference) }
+                                }
+                            }
+                        }
+                    }
                     div.block {
                         @for interpretation in &self.interpretations {
                             (interpretation)
@@ -121,17 +108,30 @@ impl Render for Rule {
     }
 }
 
-#[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize)]
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, ToSchema)]
 pub struct RuleInterpretation {
     pub article_nr: ArticleNr,
     pub index: u8,
     pub text: String,
     pub ruling: String,
+    pub references: Vec<Reference>,
+    pub changed_this_season: bool,
 }
 
 impl RuleInterpretation {
     pub fn get_title(&self) -> String {
-        format!("A.R. {}.{}", self.article_nr, self.index.to_roman())
+        self.to_ref().to_string()
+    }
+
+    pub fn to_description(&self) -> String {
+        format!("{}...", self.text.chars().take(80).collect::<String>())
+    }
+
+    pub fn to_ref(&self) -> InterpretationRef {
+        InterpretationRef {
+            article_nr: self.article_nr,
+            index: self.index,
+        }
     }
 }
 
@@ -140,15 +140,18 @@ impl Render for RuleInterpretation {
         html! {
             article.message ."is-size-5" .is-info {
                 div.message-header {
-                    p { (self.get_title()) }
+                    p { a href=(self.to_ref().to_url("")) { (self.get_title()) } }
+                    @if self.changed_this_season {
+                        span.tag .is-warning { "Geändert" }
+                    }
                 }
                 div.message-body {
-                    p { (self.text) }
+                    p { (render_with_links(&self.text)) }
                     p {
                         details {
                             summary { b { "Regelung" } }
                             p {
-                                (self.ruling)
+                                (render_with_links(&self.ruling))
                             }
                         }
                     }
@@ -158,7 +161,127 @@ impl Render for RuleInterpretation {
     }
 }
 
-#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq, Serialize)]
+/// Reference to an article or to an item of its listings like "7.1.5.a.4"
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, ToSchema)]
+pub struct RuleRef {
+    pub article_nr: ArticleNr,
+    pub item_path: Vec<String>,
+}
+
+impl RuleRef {
+    pub fn from_path_parameter(path_parameter: &str) -> eyre::Result<Self> {
+        path_parameter.replace('-', ".").parse()
+    }
+
+    pub fn to_path_parameter(&self) -> String {
+        let mut path_parameter = self.article_nr.to_path_parameter();
+        for index in &self.item_path {
+            path_parameter.push('-');
+            path_parameter.push_str(index);
+        }
+        path_parameter
+    }
+
+    pub fn to_fragment(&self) -> Option<String> {
+        (!self.item_path.is_empty()).then(|| self.item_path.join("-"))
+    }
+
+    pub fn to_url(&self, base_url: &str) -> String {
+        let url = format!("{}/rule/{}", base_url, self.article_nr.to_path_parameter());
+        match self.to_fragment() {
+            Some(fragment) => format!("{url}#{fragment}"),
+            None => url,
+        }
+    }
+
+    pub fn resolve<'a>(&self, rule: &'a Rule) -> Option<&'a str> {
+        if rule.article_nr != self.article_nr {
+            None
+        } else if self.item_path.is_empty() {
+            Some(&rule.text)
+        } else {
+            rule.find_item(&self.item_path).map(|i| i.text.as_str())
+        }
+    }
+}
+
+impl From<ArticleNr> for RuleRef {
+    fn from(article_nr: ArticleNr) -> Self {
+        Self {
+            article_nr,
+            item_path: vec![],
+        }
+    }
+}
+
+impl Display for RuleRef {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.article_nr)?;
+        for index in &self.item_path {
+            write!(f, ".{index}")?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for RuleRef {
+    type Err = eyre::Report;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts: Vec<_> = s.trim().trim_end_matches('.').split('.').collect();
+        if parts.len() < 3 || parts[3..].iter().any(|p| p.is_empty()) {
+            return Err(eyre!("Invalid rule reference: {s}"));
+        }
+        Ok(Self {
+            article_nr: parts[..3].join(".").parse()?,
+            item_path: parts[3..].iter().map(|p| p.to_string()).collect(),
+        })
+    }
+}
+
+/// Reference to an interpretation like "10.2.3.V"
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct InterpretationRef {
+    pub article_nr: ArticleNr,
+    pub index: u8,
+}
+
+impl InterpretationRef {
+    pub fn from_path_parameters(article_nr: &str, index: &str) -> eyre::Result<Self> {
+        Ok(Self {
+            article_nr: article_nr.replace('-', ".").parse()?,
+            index: u8::from_roman(index).ok_or_else(|| eyre!("Invalid Roman number: {index}"))?,
+        })
+    }
+
+    pub fn resolve<'a>(
+        &self,
+        rules: &'a IndexMap<ArticleNr, Rule>,
+    ) -> Option<&'a RuleInterpretation> {
+        rules
+            .get(&self.article_nr)?
+            .interpretations
+            .iter()
+            .find(|i| i.index == self.index)
+    }
+
+    pub fn to_url(&self, base_url: &str) -> String {
+        format!(
+            "{}/interpretation/{}/{}",
+            base_url,
+            self.article_nr.to_path_parameter(),
+            self.index.to_roman()
+        )
+    }
+}
+
+impl Display for InterpretationRef {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "A.R. {}.{}", self.article_nr, self.index.to_roman())
+    }
+}
+
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
 pub struct ArticleNr(pub u8, pub u8, pub u8);
 
 impl ArticleNr {
@@ -186,6 +309,10 @@ impl ArticleNr {
     pub fn to_path_parameter(self) -> String {
         format!("{}-{}-{}", self.0, self.1, self.2)
     }
+
+    pub fn section_nr(self) -> SectionNr {
+        SectionNr(self.0, self.1)
+    }
 }
 
 impl Display for ArticleNr {
@@ -206,3 +333,36 @@ impl FromStr for ArticleNr {
         }
     }
 }
+
+/// Number of a section like "9.1", i.e. the first two parts of an article number
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq, Serialize)]
+pub struct SectionNr(pub u8, pub u8);
+
+impl SectionNr {
+    pub fn from_path_parameter(path_parameter: &str) -> eyre::Result<Self> {
+        path_parameter.replace('-', ".").parse()
+    }
+
+    pub fn to_path_parameter(self) -> String {
+        format!("{}-{}", self.0, self.1)
+    }
+}
+
+impl Display for SectionNr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}", self.0, self.1)
+    }
+}
+
+impl FromStr for SectionNr {
+    type Err = eyre::Report;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts: Vec<_> = s.trim().trim_end_matches('.').split('.').collect();
+        if let [chapter, section] = &parts[..] {
+            Ok(Self(chapter.parse()?, section.parse()?))
+        } else {
+            Err(eyre!("Invalid section number: {s}"))
+        }
+    }
+}
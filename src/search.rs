@@ -0,0 +1,353 @@
+use indexmap::IndexMap;
+use maud::{Markup, PreEscaped, Render, html};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::Serialize;
+use std::collections::HashMap;
+use utoipa::ToSchema;
+
+use crate::{
+    reference::Reference,
+    rule::{ArticleNr, Rule},
+};
+
+static RE_WORD: Lazy<Regex> = Lazy::new(|| Regex::new(r"\w+").unwrap());
+static RE_WHITESPACE: Lazy<Regex> = Lazy::new(|| Regex::new(r"\s+").unwrap());
+
+const SNIPPET_CONTEXT: usize = 80;
+
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq, Serialize)]
+pub enum Field {
+    Title,
+    Text,
+    Situation,
+    Ruling,
+}
+
+impl Field {
+    fn weight(self) -> f32 {
+        match self {
+            Field::Title => 3.0,
+            Field::Text | Field::Situation => 1.0,
+            Field::Ruling => 0.8,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Document {
+    reference: Reference,
+    title: String,
+    fields: Vec<(Field, String)>,
+}
+
+#[derive(Debug, Copy, Clone)]
+struct Posting {
+    document: usize,
+    field: Field,
+    count: u32,
+}
+
+/// In-memory inverted index over the titles and texts of all rules and interpretations
+#[derive(Debug, Clone, Default)]
+pub struct SearchIndex {
+    documents: Vec<Document>,
+    postings: HashMap<String, Vec<Posting>>,
+}
+
+impl SearchIndex {
+    pub fn new(rules: &IndexMap<ArticleNr, Rule>) -> Self {
+        let mut index = Self::default();
+        for rule in rules.values() {
+            index.add_document(Document {
+                reference: Reference::Rule(rule.article_nr.into()),
+                title: rule.to_title(),
+                fields: vec![
+                    (Field::Title, rule.title.clone()),
+                    (Field::Text, rule.text.clone()),
+                ],
+            });
+            for interpretation in &rule.interpretations {
+                index.add_document(Document {
+                    reference: Reference::Interpretation(interpretation.to_ref()),
+                    title: format!("{} {}", interpretation.get_title(), rule.title),
+                    fields: vec![
+                        (Field::Situation, interpretation.text.clone()),
+                        (Field::Ruling, interpretation.ruling.clone()),
+                    ],
+                });
+            }
+        }
+        index
+    }
+
+    fn add_document(&mut self, mut document: Document) {
+        let id = self.documents.len();
+        for (field, text) in &mut document.fields {
+            *text = RE_WHITESPACE.replace_all(text.trim(), " ").to_string();
+            let mut counts: HashMap<String, u32> = HashMap::new();
+            for word in RE_WORD.find_iter(text) {
+                *counts.entry(normalize(word.as_str())).or_default() += 1;
+            }
+            for (term, count) in counts {
+                self.postings.entry(term).or_default().push(Posting {
+                    document: id,
+                    field: *field,
+                    count,
+                });
+            }
+        }
+        self.documents.push(document);
+    }
+
+    /// Documents containing all words of the query ordered by relevance
+    pub fn search(&self, query: &str, limit: usize) -> Vec<SearchResult> {
+        let query_terms: Vec<_> = RE_WORD
+            .find_iter(query)
+            .map(|w| normalize(w.as_str()))
+            .collect();
+        if query_terms.is_empty() {
+            return vec![];
+        }
+
+        let mut scores: Option<HashMap<usize, f32>> = None;
+        for query_term in &query_terms {
+            let term_scores = self.score_term(query_term);
+            scores = Some(match scores {
+                None => term_scores,
+                Some(scores) => scores
+                    .into_iter()
+                    .filter_map(|(document, score)| {
+                        Some((document, score + term_scores.get(&document)?))
+                    })
+                    .collect(),
+            });
+        }
+
+        let mut scores: Vec<_> = scores.unwrap_or_default().into_iter().collect();
+        scores.sort_by(|(d1, s1), (d2, s2)| s2.total_cmp(s1).then(d1.cmp(d2)));
+        scores
+            .into_iter()
+            .take(limit)
+            .map(|(document, score)| {
+                let document = &self.documents[document];
+                SearchResult {
+                    reference: document.reference.clone(),
+                    title: document.title.clone(),
+                    url: document.reference.to_url(""),
+                    score,
+                    snippet: build_snippet(document, &query_terms),
+                }
+            })
+            .collect()
+    }
+
+    fn score_term(&self, query_term: &str) -> HashMap<usize, f32> {
+        let document_count = self.documents.len() as f32;
+        let mut scores: HashMap<usize, f32> = HashMap::new();
+        for (term, postings) in &self.postings {
+            let Some(match_weight) = match_term(query_term, term) else {
+                continue;
+            };
+            let mut documents: Vec<_> = postings.iter().map(|p| p.document).collect();
+            documents.dedup();
+            let idf = (document_count / documents.len() as f32).ln() + 1.0;
+            for posting in postings {
+                *scores.entry(posting.document).or_default() += match_weight
+                    * posting.field.weight()
+                    * (1.0 + (posting.count as f32).ln())
+                    * idf;
+            }
+        }
+        scores
+    }
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct SearchResult {
+    pub reference: Reference,
+    pub title: String,
+    pub url: String,
+    pub score: f32,
+    pub snippet: Snippet,
+}
+
+impl Render for SearchResult {
+    fn render(&self) -> Markup {
+        html! {
+            .box {
+                p ."is-size-5" { a href=(self.url) { (self.title) } }
+                p { (self.snippet) }
+            }
+        }
+    }
+}
+
+/// Excerpt of a matching field with the positions of the matching words
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+pub struct Snippet {
+    pub text: String,
+    pub highlights: Vec<Highlight>,
+}
+
+/// Byte range of a matching word inside the text of a snippet
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, ToSchema)]
+pub struct Highlight {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Render for Snippet {
+    fn render(&self) -> Markup {
+        let mut markup = String::new();
+        let mut last_end = 0;
+        for highlight in &self.highlights {
+            markup.push_str(&html! { (self.text[last_end..highlight.start]) }.into_string());
+            markup.push_str(
+                &html! { mark { (self.text[highlight.start..highlight.end]) } }.into_string(),
+            );
+            last_end = highlight.end;
+        }
+        markup.push_str(&html! { (self.text[last_end..]) }.into_string());
+        PreEscaped(markup)
+    }
+}
+
+fn build_snippet(document: &Document, query_terms: &[String]) -> Snippet {
+    let is_match = |word: &str| {
+        let word = normalize(word);
+        query_terms.iter().any(|t| match_term(t, &word).is_some())
+    };
+    // Prefer an excerpt of the text over the title that is shown anyway
+    let (text, first_match) = document
+        .fields
+        .iter()
+        .filter(|(field, _)| *field != Field::Title)
+        .chain(
+            document
+                .fields
+                .iter()
+                .filter(|(field, _)| *field == Field::Title),
+        )
+        .find_map(|(_, text)| {
+            RE_WORD
+                .find_iter(text)
+                .find(|w| is_match(w.as_str()))
+                .map(|w| (text, w.start()))
+        })
+        .unwrap_or((&document.fields[0].1, 0));
+
+    let mut start = first_match.saturating_sub(SNIPPET_CONTEXT);
+    while !text.is_char_boundary(start) {
+        start -= 1;
+    }
+    if start > 0 {
+        start = text[start..first_match]
+            .find(' ')
+            .map_or(first_match, |i| start + i + 1);
+    }
+    let mut end = (first_match + 2 * SNIPPET_CONTEXT).min(text.len());
+    while !text.is_char_boundary(end) {
+        end += 1;
+    }
+    if end < text.len() {
+        end = text[start..end].rfind(' ').map_or(end, |i| start + i);
+    }
+
+    let prefix = if start > 0 { "… " } else { "" };
+    let suffix = if end < text.len() { " …" } else { "" };
+    let highlights = RE_WORD
+        .find_iter(&text[start..end])
+        .filter(|w| is_match(w.as_str()))
+        .map(|w| Highlight {
+            start: w.start() + prefix.len(),
+            end: w.end() + prefix.len(),
+        })
+        .collect();
+    Snippet {
+        text: format!("{prefix}{}{suffix}", &text[start..end]),
+        highlights,
+    }
+}
+
+/// Folds case, umlauts and ß so that e.g. "Stoß", "STOSS" and "stoss" are the same term
+pub fn normalize(word: &str) -> String {
+    let mut normalized = String::with_capacity(word.len());
+    for c in word.chars().flat_map(char::to_lowercase) {
+        match c {
+            'ä' => normalized.push_str("ae"),
+            'ö' => normalized.push_str("oe"),
+            'ü' => normalized.push_str("ue"),
+            'ß' => normalized.push_str("ss"),
+            c => normalized.push(c),
+        }
+    }
+    normalized
+}
+
+/// Weight of an indexed term for a query term, parts of compound words like "Handsignal" count less
+fn match_term(query_term: &str, term: &str) -> Option<f32> {
+    if term == query_term {
+        Some(1.0)
+    } else if query_term.chars().count() < 3 {
+        None
+    } else if term.starts_with(query_term) {
+        Some(0.8)
+    } else if term.contains(query_term) {
+        Some(0.5)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parsed_rules_fixture;
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!(normalize("Stoß"), "stoss");
+        assert_eq!(normalize("STOSS"), "stoss");
+        assert_eq!(normalize("Rückseite"), "rueckseite");
+    }
+
+    #[test]
+    fn test_search() {
+        let rules = parsed_rules_fixture();
+        let index = SearchIndex::new(rules);
+
+        let results = index.search("Fair Catch-Signal", 10);
+        assert_eq!(results.len(), 10);
+        assert!(
+            results
+                .iter()
+                .all(|r| r.snippet.highlights.len() >= 2 && r.score > 0.0)
+        );
+        assert!(results.windows(2).all(|r| r[0].score >= r[1].score));
+        let snippet = &results[0].snippet;
+        assert!(
+            snippet
+                .highlights
+                .iter()
+                .any(|h| normalize(&snippet.text[h.start..h.end]) == "signal")
+        );
+
+        assert_eq!(
+            index
+                .search("rueckseite", 100)
+                .iter()
+                .map(|r| &r.reference)
+                .collect::<Vec<_>>(),
+            index
+                .search("RÜCKSEITE", 100)
+                .iter()
+                .map(|r| &r.reference)
+                .collect::<Vec<_>>()
+        );
+        assert!(!index.search("Rückseite", 100).is_empty());
+        // Part of compound words like "Handsignal"
+        assert!(index.search("signal", 1000).len() > index.search("Fair Catch-Signal", 1000).len());
+        assert!(index.search("Quidditch", 10).is_empty());
+    }
+}
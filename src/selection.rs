@@ -0,0 +1,214 @@
+use indexmap::IndexMap;
+use rand::{RngExt, seq::SliceRandom};
+use rand_pcg::Pcg64;
+use serde::Deserialize;
+use std::{collections::HashMap, fmt::Debug};
+
+use crate::{
+    rotation::{RotationContent, RotationItem},
+    rule::{ArticleNr, Rule},
+};
+
+const MAX_WEIGHT: u32 = 10;
+const MAX_FACTOR: usize = 4;
+const CHARS_PER_WEIGHT: usize = 1000;
+
+/// Order in which the items of a pool are shown during one cycle of a rotation
+///
+/// The order must only depend on the random number generator, so that all users see the same
+/// item at the same date.
+pub trait SelectionStrategy: Debug + Send + Sync {
+    /// Items of one cycle in order, an item may occur more than once
+    fn cycle_order(&self, pool: &[RotationItem], rng: &mut Pcg64) -> Vec<RotationItem>;
+}
+
+/// Every item once per cycle in a uniformly shuffled order
+#[derive(Debug, Copy, Clone, Default)]
+pub struct Shuffle;
+
+impl SelectionStrategy for Shuffle {
+    fn cycle_order(&self, pool: &[RotationItem], rng: &mut Pcg64) -> Vec<RotationItem> {
+        let mut order = pool.to_vec();
+        order.shuffle(rng);
+        order
+    }
+}
+
+/// Every item as often per cycle as its weight with the occurrences spread over the cycle
+#[derive(Debug, Clone, Default)]
+pub struct Weighted {
+    weights: HashMap<RotationItem, u32>,
+}
+
+impl Weighted {
+    pub fn new(
+        pool: &[RotationItem],
+        rules: &IndexMap<ArticleNr, Rule>,
+        weighting: &Weighting,
+    ) -> Self {
+        Self {
+            weights: pool
+                .iter()
+                .filter_map(|item| Some((*item, weighting.weight(&item.resolve(rules)?))))
+                .collect(),
+        }
+    }
+
+    pub fn weight(&self, item: &RotationItem) -> u32 {
+        self.weights.get(item).copied().unwrap_or(1)
+    }
+}
+
+impl SelectionStrategy for Weighted {
+    fn cycle_order(&self, pool: &[RotationItem], rng: &mut Pcg64) -> Vec<RotationItem> {
+        let mut positions = vec![];
+        for item in pool {
+            let weight = self.weight(item);
+            let offset: f64 = rng.random_range(0.0..1.0);
+            for occurrence in 0..weight {
+                positions.push(((f64::from(occurrence) + offset) / f64::from(weight), *item));
+            }
+        }
+        positions.sort_by(|(p1, _), (p2, _)| p1.total_cmp(p2));
+        positions.into_iter().map(|(_, item)| item).collect()
+    }
+}
+
+#[derive(Debug, Copy, Clone, Deserialize)]
+pub struct ChapterWeight {
+    pub chapter: u8,
+    pub weight: u32,
+}
+
+/// Factors that make items occur more often than once per cycle
+#[derive(Debug, Clone, Deserialize)]
+pub struct Weighting {
+    /// Weights of single chapters, all other chapters have the weight 1
+    #[serde(default)]
+    pub chapters: Vec<ChapterWeight>,
+    /// Longer rules and interpretations occur more often
+    #[serde(default)]
+    pub length: bool,
+    /// Rules with more interpretations occur more often
+    #[serde(default)]
+    pub interpretations: bool,
+    /// Factor for rules and interpretations that were changed this season
+    #[serde(default = "default_changed_this_season")]
+    pub changed_this_season: u32,
+}
+
+fn default_changed_this_season() -> u32 {
+    1
+}
+
+impl Default for Weighting {
+    fn default() -> Self {
+        Self {
+            chapters: vec![],
+            length: false,
+            interpretations: false,
+            changed_this_season: default_changed_this_season(),
+        }
+    }
+}
+
+impl Weighting {
+    pub fn weight(&self, content: &RotationContent) -> u32 {
+        let (article_nr, changed_this_season, length, interpretation_count) = match content {
+            RotationContent::Rule(rule) => (
+                rule.article_nr,
+                rule.changed_this_season,
+                rule.text.chars().count(),
+                rule.interpretations.len(),
+            ),
+            RotationContent::Interpretation(_, interpretation) => (
+                interpretation.article_nr,
+                interpretation.changed_this_season,
+                interpretation.text.chars().count() + interpretation.ruling.chars().count(),
+                0,
+            ),
+        };
+        let mut weight = self
+            .chapters
+            .iter()
+            .find(|c| c.chapter == article_nr.0)
+            .map_or(1, |c| c.weight);
+        if changed_this_season {
+            weight *= self.changed_this_season;
+        }
+        if self.length {
+            weight *= (1 + length / CHARS_PER_WEIGHT).min(MAX_FACTOR) as u32;
+        }
+        if self.interpretations {
+            weight *= (1 + interpretation_count).min(MAX_FACTOR) as u32;
+        }
+        weight.clamp(1, MAX_WEIGHT)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parsed_rules_fixture;
+    use rand_seeder::Seeder;
+
+    #[test]
+    fn test_weighted() {
+        let rules = parsed_rules_fixture();
+        let pool: Vec<_> = rules.keys().map(|a| RotationItem::Rule(*a)).collect();
+        let weighting = Weighting {
+            chapters: vec![ChapterWeight {
+                chapter: 9,
+                weight: 3,
+            }],
+            changed_this_season: 2,
+            ..Default::default()
+        };
+        let strategy = Weighted::new(&pool, rules, &weighting);
+
+        let changed_foul = rules
+            .values()
+            .find(|r| r.article_nr.0 == 9 && r.changed_this_season)
+            .unwrap();
+        assert_eq!(
+            strategy.weight(&RotationItem::Rule(changed_foul.article_nr)),
+            6
+        );
+        assert_eq!(strategy.weight(&RotationItem::Rule(ArticleNr(1, 1, 1))), 1);
+
+        let order = strategy.cycle_order(&pool, &mut Seeder::from("test").into_rng());
+        let count = |article_nr| {
+            order
+                .iter()
+                .filter(|i| **i == RotationItem::Rule(article_nr))
+                .count()
+        };
+        assert_eq!(count(changed_foul.article_nr), 6);
+        assert_eq!(count(ArticleNr(1, 1, 1)), 1);
+        assert_eq!(
+            order.len() as u32,
+            pool.iter().map(|i| strategy.weight(i)).sum::<u32>()
+        );
+        assert!(order.windows(2).all(|w| w[0] != w[1]));
+        // The same seed always leads to the same order
+        assert_eq!(
+            order,
+            strategy.cycle_order(&pool, &mut Seeder::from("test").into_rng())
+        );
+
+        let weighting = Weighting {
+            length: true,
+            interpretations: true,
+            ..Default::default()
+        };
+        let longest = rules
+            .values()
+            .max_by_key(|r| r.text.chars().count())
+            .unwrap();
+        assert!(weighting.weight(&RotationContent::Rule(longest)) > 1);
+        assert_eq!(
+            Weighting::default().weight(&RotationContent::Rule(longest)),
+            1
+        );
+    }
+}
@@ -0,0 +1,51 @@
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::ops::Range;
+
+static RE_SIGNAL_REFERENCE: Lazy<Regex> = Lazy::new(|| Regex::new(r"\[S\d+[^\]]*\]").unwrap());
+static RE_SIGNAL: Lazy<Regex> = Lazy::new(|| Regex::new(r"\bS(?<nr>\d+)\b").unwrap());
+
+/// Official referee signal
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Signal {
+    pub nr: u8,
+    pub name: String,
+}
+
+impl Signal {
+    pub fn to_title(&self) -> String {
+        format!("S{} {}", self.nr, self.name)
+    }
+
+    pub fn to_url(&self, base_url: &str) -> String {
+        format!("{}/signal/{}", base_url, self.nr)
+    }
+}
+
+/// Numbers of all signals referenced like "[S7 und S21]" inside the text
+pub fn find_signal_references(text: &str) -> Vec<u8> {
+    RE_SIGNAL_REFERENCE
+        .find_iter(text)
+        .flat_map(|reference| RE_SIGNAL.captures_iter(reference.as_str()))
+        .filter_map(|c| c["nr"].parse().ok())
+        .collect()
+}
+
+/// Positions of all signals referenced inside the text with their number
+pub fn find_signal_spans(text: &str) -> Vec<(Range<usize>, u8)> {
+    RE_SIGNAL_REFERENCE
+        .find_iter(text)
+        .flat_map(|reference| {
+            RE_SIGNAL
+                .captures_iter(reference.as_str())
+                .filter_map(move |c| {
+                    let signal = c.get(0).unwrap();
+                    Some((
+                        reference.start() + signal.start()..reference.start() + signal.end(),
+                        c["nr"].parse().ok()?,
+                    ))
+                })
+        })
+        .collect()
+}
@@ -0,0 +1,128 @@
+use eyre::eyre;
+use maud::{Render, html};
+use serde::Serialize;
+use std::{fmt::Display, str::FromStr};
+
+use crate::{reference::render_standard_with_links, rule::SectionNr};
+
+/// Section of the officiating standards in part II of the rule book
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize)]
+pub struct StandardSection {
+    pub section_nr: SectionNr,
+    pub title: String,
+    pub introduction: Option<String>,
+    pub standards: Vec<Standard>,
+}
+
+impl StandardSection {
+    pub fn to_title(&self) -> String {
+        format!("Abschnitt {} {}", self.section_nr, self.title)
+    }
+
+    pub fn to_url(&self, base_url: &str) -> String {
+        format!(
+            "{}/standards/{}",
+            base_url,
+            self.section_nr.to_path_parameter()
+        )
+    }
+}
+
+impl Render for StandardSection {
+    fn render(&self) -> maud::Markup {
+        html! {
+            article.message ."is-size-4" .is-warning {
+                div.message-header {
+                    p { a href=(self.to_url("")) { (self.to_title()) } }
+                }
+                div.message-body {
+                    .content {
+                        @if let Some(introduction) = &self.introduction {
+                            p { (render_standard_with_links(introduction)) }
+                        }
+                        ol {
+                            @for standard in &self.standards {
+                                li id=(standard.standard_nr.2) {
+                                    (render_standard_with_links(&standard.text))
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize)]
+pub struct Standard {
+    pub standard_nr: StandardNr,
+    pub section_title: String,
+    pub text: String,
+}
+
+impl Standard {
+    pub fn to_title(&self) -> String {
+        format!("Standard {} {}", self.standard_nr, self.section_title)
+    }
+
+    pub fn to_url(&self, base_url: &str) -> String {
+        format!(
+            "{}/standards/{}/{}",
+            base_url,
+            self.standard_nr.section_nr().to_path_parameter(),
+            self.standard_nr.2
+        )
+    }
+}
+
+impl Render for Standard {
+    fn render(&self) -> maud::Markup {
+        html! {
+            article.message ."is-size-4" .is-warning {
+                div.message-header {
+                    p { (self.to_title()) }
+                }
+                div.message-body {
+                    .content {
+                        p { (render_standard_with_links(&self.text)) }
+                    }
+                    div.block {
+                        a .button .is-medium href=(format!("/standards/{}", self.standard_nr.section_nr().to_path_parameter())) {
+                            "Ganzer Abschnitt"
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Number of a single standard like "1.9.1", i.e. the third part is the index inside the section
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq, Serialize)]
+pub struct StandardNr(pub u8, pub u8, pub u8);
+
+impl StandardNr {
+    pub fn section_nr(self) -> SectionNr {
+        SectionNr(self.0, self.1)
+    }
+}
+
+impl Display for StandardNr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}.{}", self.0, self.1, self.2)
+    }
+}
+
+impl FromStr for StandardNr {
+    type Err = eyre::Report;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts: Vec<_> = s.trim().trim_end_matches('.').split('.').collect();
+        if let [chapter, section, index] = &parts[..] {
+            Ok(Self(chapter.parse()?, section.parse()?, index.parse()?))
+        } else {
+            Err(eyre!("Invalid standard number: {s}"))
+        }
+    }
+}